@@ -0,0 +1,139 @@
+use crate::HM;
+use crate::holidays::HolidayEntry;
+use chrono::{Datelike, NaiveDate, Weekday};
+
+pub fn is_weekend(date: NaiveDate) -> bool {
+    matches!(date.weekday(), Weekday::Sat | Weekday::Sun)
+}
+
+pub fn fiscal_quarter(date: NaiveDate, fiscal_start: u32) -> u32 {
+    let offset = (date.month() + 12 - fiscal_start) % 12;
+    offset / 3 + 1
+}
+
+pub fn quarter_months(date: NaiveDate, fiscal_start: u32, quarter: Option<u32>) -> Vec<(u32, i32)> {
+    let fiscal_year = if date.month() >= fiscal_start {
+        date.year()
+    } else {
+        date.year() - 1
+    };
+    let quarter = quarter.unwrap_or_else(|| fiscal_quarter(date, fiscal_start));
+    let first = fiscal_start - 1 + (quarter - 1) * 3;
+    (first..first + 3)
+        .map(|index| (index % 12 + 1, fiscal_year + (index / 12) as i32))
+        .collect()
+}
+
+pub fn working_days(year: i32, month: u32, hm: &HM) -> f64 {
+    let Some(first) = NaiveDate::from_ymd_opt(year, month, 1) else {
+        return 0.0;
+    };
+    first
+        .iter_days()
+        .take_while(|date| date.month() == month)
+        .filter(|date| !is_weekend(*date))
+        .map(|date| {
+            1.0 - hm
+                .get(&(date.day(), month))
+                .map_or(0.0, HolidayEntry::day_off)
+        })
+        .sum()
+}
+
+pub fn working_days_in_year(year: i32, hm: &HM) -> f64 {
+    (1..=12).map(|month| working_days(year, month, hm)).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).expect("valid date")
+    }
+
+    #[test]
+    fn quarter_months_uses_calendar_quarters_by_default() {
+        assert_eq!(
+            quarter_months(date(2026, 11, 5), 1, None),
+            vec![(10, 2026), (11, 2026), (12, 2026)]
+        );
+        assert_eq!(
+            quarter_months(date(2026, 1, 1), 1, None),
+            vec![(1, 2026), (2, 2026), (3, 2026)]
+        );
+    }
+
+    #[test]
+    fn quarter_months_follows_fiscal_year_start() {
+        assert_eq!(fiscal_quarter(date(2026, 5, 1), 4), 1);
+        assert_eq!(fiscal_quarter(date(2027, 2, 1), 4), 4);
+        assert_eq!(
+            quarter_months(date(2027, 2, 1), 4, None),
+            vec![(1, 2027), (2, 2027), (3, 2027)]
+        );
+    }
+
+    #[test]
+    fn quarter_months_wraps_into_next_calendar_year() {
+        assert_eq!(
+            quarter_months(date(2026, 12, 15), 11, None),
+            vec![(11, 2026), (12, 2026), (1, 2027)]
+        );
+        assert_eq!(
+            quarter_months(date(2027, 1, 15), 11, Some(1)),
+            vec![(11, 2026), (12, 2026), (1, 2027)]
+        );
+    }
+
+    #[test]
+    fn quarter_months_selects_explicit_quarter_in_current_fiscal_year() {
+        assert_eq!(
+            quarter_months(date(2026, 2, 1), 4, Some(3)),
+            vec![(10, 2025), (11, 2025), (12, 2025)]
+        );
+        assert_eq!(
+            quarter_months(date(2026, 6, 1), 1, Some(4)),
+            vec![(10, 2026), (11, 2026), (12, 2026)]
+        );
+    }
+
+    #[test]
+    fn working_days_skip_weekends_and_days_off() {
+        let mut hm = HM::new();
+        hm.insert((1, 1), HolidayEntry::official("New Year's Day"));
+        hm.insert((3, 1), HolidayEntry::official("Saturday holiday"));
+        hm.insert((5, 1), HolidayEntry::school("Winter break"));
+        hm.insert((6, 1), HolidayEntry::custom("Epiphany off"));
+
+        assert_eq!(working_days(2026, 1, &HM::new()), 22.0);
+        assert_eq!(working_days(2026, 1, &hm), 20.0);
+        assert_eq!(working_days(2026, 13, &hm), 0.0);
+        assert_eq!(working_days_in_year(2026, &HM::new()), 261.0);
+    }
+
+    #[test]
+    fn working_days_count_half_days_as_half() {
+        let mut hm = HM::new();
+        hm.insert(
+            (24, 12),
+            HolidayEntry {
+                half_day: true,
+                ..HolidayEntry::custom("Christmas Eve")
+            },
+        );
+        hm.insert((25, 12), HolidayEntry::official("Christmas"));
+        hm.insert(
+            (31, 12),
+            HolidayEntry {
+                half_day: true,
+                ..HolidayEntry::custom("New Year's Eve")
+            },
+        );
+
+        assert_eq!(working_days(2026, 12, &HM::new()), 23.0);
+        assert_eq!(working_days(2026, 12, &hm), 21.0);
+        hm.remove(&(31, 12));
+        assert_eq!(working_days(2026, 12, &hm), 21.5);
+    }
+}
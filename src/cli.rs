@@ -1,9 +1,18 @@
 mod actions;
 
+use chrono::{Datelike, NaiveDate};
 use clap::{Parser, Subcommand, ValueEnum};
+use std::fs;
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
 
-use crate::error::Result;
-use crate::holidays::Provider;
+use crate::config::Config;
+use crate::dates;
+use crate::error::{CalError, Result};
+use crate::holidays::{HolidayKind, Provider, parse_language};
+use crate::locale;
+use crate::recurring::Recurrence;
+use crate::template::Template;
 
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None)]
@@ -12,6 +21,24 @@ pub struct Args {
     #[arg(long, value_name = "COUNTRY", global = true)]
     pub country: Option<String>,
 
+    #[arg(long, value_name = "LOCALE", global = true)]
+    pub locale: Option<String>,
+
+    #[arg(long, value_name = "LANG", global = true)]
+    pub lang: Option<String>,
+
+    #[arg(long, value_name = "REGION", global = true)]
+    pub region: Option<String>,
+
+    #[arg(long, value_enum, value_delimiter = ',', global = true)]
+    pub include: Vec<Include>,
+
+    #[arg(long, value_name = "NAME", global = true, value_parser = parse_person)]
+    pub person: Option<String>,
+
+    #[arg(long, value_name = "PATH", global = true)]
+    pub team_file: Option<PathBuf>,
+
     #[command(subcommand)]
     pub action: Option<Commands>,
 }
@@ -19,24 +46,183 @@ pub struct Args {
 #[derive(Subcommand, Debug)]
 pub enum Commands {
     Add {
-        day: u32,
-        month: u32,
+        #[arg(required = true, num_args = 1..=2, value_name = "DATE | DAY MONTH")]
+        date: Vec<String>,
         #[arg(long)]
         description: Option<String>,
+        #[arg(long, value_enum)]
+        repeat: Option<Recurrence>,
+        #[arg(long)]
+        year: Option<i32>,
+        #[arg(long, conflicts_with = "repeat")]
+        half_day: bool,
+        #[arg(long = "tag", value_name = "TAG", value_parser = parse_tag, conflicts_with = "repeat")]
+        tags: Vec<String>,
+    },
+    Rules {
+        #[arg(long, value_name = "N")]
+        remove: Option<usize>,
     },
     Delete {
-        day: u32,
-        month: u32,
+        #[arg(required = true, num_args = 1..=2, value_name = "DATE | DAY MONTH")]
+        date: Vec<String>,
+        #[arg(long)]
+        year: Option<i32>,
+    },
+    Hide {
+        date: String,
+    },
+    Unhide {
+        date: String,
+    },
+    Edit {
+        date: String,
+        #[arg(long)]
+        description: Option<String>,
+        #[arg(long, value_name = "DATE")]
+        move_to: Option<String>,
+        #[arg(long = "type", value_enum)]
+        kind: Option<EntryType>,
     },
     List {
+        #[arg(long)]
+        all: bool,
         #[arg(long, value_enum, default_value_t = OutputFormat::default())]
         format: OutputFormat,
+        #[arg(long, value_enum, value_delimiter = ',')]
+        columns: Option<Vec<ListColumn>>,
+        #[arg(long, conflicts_with_all = ["format", "columns", "template_file"])]
+        template: Option<String>,
+        #[arg(long, value_name = "PATH", conflicts_with_all = ["format", "columns"])]
+        template_file: Option<PathBuf>,
+        #[arg(long = "tag", value_name = "TAG", value_parser = parse_tag)]
+        tags: Vec<String>,
+    },
+    Compare {
+        #[arg(required = true, num_args = 2..)]
+        countries: Vec<String>,
+        #[arg(long)]
+        year: Option<i32>,
+    },
+    Stats {
+        #[arg(long)]
+        year: Option<i32>,
+    },
+    Team {
+        #[arg(long, value_name = "MONTH", value_parser = clap::value_parser!(u32).range(1..=12))]
+        month: Option<u32>,
+        #[arg(long)]
+        year: Option<i32>,
+    },
+    Search {
+        pattern: String,
+        #[arg(long, value_name = "FROM..TO", value_parser = parse_years)]
+        years: Option<RangeInclusive<i32>>,
+        #[arg(long)]
+        regex: bool,
     },
     Display {
         mode: Option<Mode>,
+        #[arg(long, value_enum, default_value_t = DisplayFormat::default())]
+        format: DisplayFormat,
+        #[arg(long, value_enum, default_value_t = Paper::default())]
+        paper: Paper,
+        #[arg(long, value_name = "COLUMNS")]
+        width: Option<usize>,
+        #[arg(long, value_enum, ignore_case = true)]
+        quarter: Option<Quarter>,
+        #[arg(long, value_name = "MONTH", value_parser = clap::value_parser!(u32).range(1..=12))]
+        fiscal_start: Option<u32>,
+        #[arg(long = "tag", value_name = "TAG", value_parser = parse_tag)]
+        tags: Vec<String>,
     },
 }
 
+fn parse_date_args(
+    parts: &[String],
+    today: NaiveDate,
+    locale: Option<chrono::Locale>,
+    year: Option<i32>,
+) -> Result<NaiveDate> {
+    let default_year = year.unwrap_or(today.year());
+    let date = if let [day, month] = parts
+        && let (Ok(day), Ok(month)) = (day.parse::<u32>(), month.parse::<u32>())
+    {
+        dates::from_day_month(day, month, default_year)?
+    } else {
+        dates::parse_in_year(&parts.join(" "), today, locale, default_year)?
+    };
+    if let Some(year) = year
+        && date.year() != year
+    {
+        return Err(CalError::InvalidDate(format!(
+            "{date} is not in {year}; give a day and month with --year"
+        )));
+    }
+    Ok(date)
+}
+
+fn parse_word(value: &str, what: &str) -> std::result::Result<String, String> {
+    let word = value.trim().to_lowercase();
+    if word.is_empty()
+        || !word
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(format!(
+            "invalid {what} {value:?}; use letters, digits, '-' and '_'"
+        ));
+    }
+    Ok(word)
+}
+
+fn parse_tag(value: &str) -> std::result::Result<String, String> {
+    parse_word(value.trim().trim_start_matches('#'), "tag")
+}
+
+fn parse_person(value: &str) -> std::result::Result<String, String> {
+    parse_word(value, "person")
+}
+
+fn parse_years(value: &str) -> std::result::Result<RangeInclusive<i32>, String> {
+    let parse = |year: &str| {
+        year.trim()
+            .parse::<i32>()
+            .map_err(|_| format!("invalid year {year:?}"))
+    };
+    let (from, to) = match value.split_once("..") {
+        Some((from, to)) => (parse(from)?, parse(to.strip_prefix('=').unwrap_or(to))?),
+        None => {
+            let year = parse(value)?;
+            (year, year)
+        }
+    };
+    if from > to {
+        return Err(format!("{from} comes after {to}"));
+    }
+    Ok(from..=to)
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+pub enum EntryType {
+    Custom,
+    Leave,
+}
+
+impl EntryType {
+    pub fn kind(self) -> HolidayKind {
+        match self {
+            EntryType::Custom => HolidayKind::Custom,
+            EntryType::Leave => HolidayKind::Leave,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+pub enum Include {
+    School,
+}
+
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
 pub enum Mode {
     Q,
@@ -44,32 +230,275 @@ pub enum Mode {
     Year,
 }
 
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug, Default)]
+pub enum DisplayFormat {
+    #[default]
+    Terminal,
+    Html,
+    Json,
+    Svg,
+    Pdf,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug, Default)]
+pub enum Paper {
+    #[default]
+    A4,
+    Letter,
+}
+
+impl Paper {
+    pub fn size(self) -> (f64, f64) {
+        match self {
+            Paper::A4 => (595.0, 842.0),
+            Paper::Letter => (612.0, 792.0),
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+pub enum Quarter {
+    Q1,
+    Q2,
+    Q3,
+    Q4,
+}
+
+impl Quarter {
+    pub fn number(self) -> u32 {
+        match self {
+            Quarter::Q1 => 1,
+            Quarter::Q2 => 2,
+            Quarter::Q3 => 3,
+            Quarter::Q4 => 4,
+        }
+    }
+}
+
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug, Default)]
 pub enum OutputFormat {
     #[default]
     Table,
     Json,
     Markdown,
+    Csv,
+    Tsv,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+pub enum ListColumn {
+    Date,
+    Weekday,
+    Name,
+    Kind,
+    Provider,
+    Regions,
+    Countries,
+    Tags,
+}
+
+impl ListColumn {
+    pub const DEFAULT: [ListColumn; 3] = [ListColumn::Date, ListColumn::Name, ListColumn::Kind];
+
+    pub fn header(self) -> &'static str {
+        match self {
+            ListColumn::Date => "date",
+            ListColumn::Weekday => "weekday",
+            ListColumn::Name => "name",
+            ListColumn::Kind => "kind",
+            ListColumn::Provider => "provider",
+            ListColumn::Regions => "regions",
+            ListColumn::Countries => "countries",
+            ListColumn::Tags => "tags",
+        }
+    }
 }
 
 impl Args {
     pub fn invoke(&self) -> Result<()> {
-        let provider = Provider::from_country(self.country.clone())?;
-        let env = actions::RealEnvironment::new(provider);
-        self.dispatch(&env)
+        let mut providers = Provider::list_from_args(self.country.clone(), self.region.clone())?;
+        let provider = providers.remove(0);
+        let config = Config::load()?;
+        let language = match self.lang.as_deref().or(config.language.as_deref()) {
+            Some(language) => Some(parse_language(language)?),
+            None => None,
+        };
+        let env = actions::RealEnvironment::new(provider)
+            .with_language(language)
+            .with_school_holidays(self.include.contains(&Include::School))
+            .with_overlay(providers)
+            .with_person(self.person.clone())
+            .with_team_file(self.team_file.clone().or_else(|| {
+                config
+                    .team_file
+                    .as_deref()
+                    .map(|path| PathBuf::from(shellexpand::tilde(path).as_ref()))
+            }));
+        self.dispatch(&env, &config)
     }
 
-    fn dispatch<E: actions::ActionEnvironment>(&self, env: &E) -> Result<()> {
+    fn dispatch<E: actions::ActionEnvironment>(&self, env: &E, config: &Config) -> Result<()> {
+        let locale = match self.locale.as_deref().or(config.locale.as_deref()) {
+            Some(name) => Some(locale::parse(name)?),
+            None => None,
+        };
         match self.action.as_ref() {
-            Some(Commands::Delete { day, month }) => actions::delete(env, *day, *month),
+            Some(Commands::Delete { date, year }) => actions::delete(
+                env,
+                parse_date_args(date, env.now().date_naive(), locale, *year)?,
+            ),
             Some(Commands::Add {
-                day,
-                month,
+                date,
                 description,
-            }) => actions::add(env, *day, *month, description.clone()),
-            Some(Commands::Display { mode }) => actions::display(env, (*mode).unwrap_or(Mode::Q)),
-            Some(Commands::List { format }) => actions::list(env, *format),
-            None => actions::display(env, Mode::Q),
+                repeat,
+                year,
+                half_day,
+                tags,
+            }) => {
+                let date = parse_date_args(date, env.now().date_naive(), locale, *year)?;
+                match repeat {
+                    Some(recurrence) => {
+                        actions::add_rule(env, date, description.clone(), *recurrence)
+                    }
+                    None => {
+                        let options = actions::AddOptions {
+                            description: description.clone(),
+                            half_day: *half_day,
+                            tags: tags.clone(),
+                        };
+                        actions::add(env, date, &options)
+                    }
+                }
+            }
+            Some(Commands::Rules { remove }) => actions::rules(env, *remove),
+            Some(Commands::Compare { countries, year }) => {
+                let providers = Provider::list_from_args(Some(countries.join(",")), None)?;
+                actions::compare(env, &providers, *year)
+            }
+            Some(Commands::Hide { date }) => {
+                actions::hide(env, dates::parse(date, env.now().date_naive(), locale)?)
+            }
+            Some(Commands::Unhide { date }) => {
+                actions::unhide(env, dates::parse(date, env.now().date_naive(), locale)?)
+            }
+            Some(Commands::Edit {
+                date,
+                description,
+                move_to,
+                kind,
+            }) => {
+                if description.is_none() && move_to.is_none() && kind.is_none() {
+                    return Err(CalError::Config(
+                        "edit needs --description, --move-to or --type".to_string(),
+                    ));
+                }
+                let today = env.now().date_naive();
+                let options = actions::EditOptions {
+                    description: description.clone(),
+                    move_to: match move_to {
+                        Some(target) => Some(dates::parse(target, today, locale)?),
+                        None => None,
+                    },
+                    kind: kind.map(EntryType::kind),
+                };
+                actions::edit(env, dates::parse(date, today, locale)?, &options)
+            }
+            Some(Commands::Stats { year }) => actions::stats(env, *year, locale),
+            Some(Commands::Team { month, year }) => {
+                if self.person.is_some() {
+                    return Err(CalError::Config(
+                        "team shows every person; drop --person".to_string(),
+                    ));
+                }
+                actions::team(env, *month, *year, locale)
+            }
+            Some(Commands::Search {
+                pattern,
+                years,
+                regex,
+            }) => {
+                let options = actions::SearchOptions {
+                    pattern: pattern.clone(),
+                    years: years.clone(),
+                    regex: *regex,
+                };
+                actions::search(env, &options)
+            }
+            Some(Commands::Display {
+                mode,
+                format,
+                paper,
+                width,
+                quarter,
+                fiscal_start,
+                tags,
+            }) => {
+                if quarter.is_some() && !matches!(mode, None | Some(Mode::Q)) {
+                    return Err(CalError::Config(
+                        "--quarter can only be used with the q display mode".to_string(),
+                    ));
+                }
+                let fiscal_start = match fiscal_start {
+                    Some(month) => *month,
+                    None => config.fiscal_year_start()?,
+                };
+                let options = actions::DisplayOptions {
+                    mode: (*mode).unwrap_or(Mode::Q),
+                    format: *format,
+                    locale,
+                    paper: *paper,
+                    width: *width,
+                    quarter: *quarter,
+                    fiscal_start,
+                    tags: tags.clone(),
+                    tag_colors: config.tag_colors()?,
+                };
+                actions::display(env, &options)
+            }
+            Some(Commands::List {
+                all,
+                format,
+                columns,
+                template,
+                template_file,
+                tags,
+            }) => {
+                if columns.is_some() && !matches!(format, OutputFormat::Csv | OutputFormat::Tsv) {
+                    return Err(CalError::Config(
+                        "--columns can only be used with --format csv or tsv".to_string(),
+                    ));
+                }
+                let template = match (template, template_file) {
+                    (Some(source), _) => Some(Template::parse(source)?),
+                    (None, Some(path)) => {
+                        let source = fs::read_to_string(path)?;
+                        Some(Template::parse(
+                            source.strip_suffix('\n').unwrap_or(&source),
+                        )?)
+                    }
+                    (None, None) => None,
+                };
+                let options = actions::ListOptions {
+                    all: *all,
+                    format: *format,
+                    locale,
+                    columns: columns
+                        .clone()
+                        .unwrap_or_else(|| ListColumn::DEFAULT.to_vec()),
+                    template,
+                    tags: tags.clone(),
+                };
+                actions::list(env, &options)
+            }
+            None => {
+                let options = actions::DisplayOptions {
+                    locale,
+                    fiscal_start: config.fiscal_year_start()?,
+                    tag_colors: config.tag_colors()?,
+                    ..actions::DisplayOptions::default()
+                };
+                actions::display(env, &options)
+            }
         }
     }
 }
@@ -79,7 +508,8 @@ mod tests {
     use super::*;
     use crate::HM;
     use crate::cli::actions::ActionEnvironment;
-    use crate::holidays::{HolidayEntry, HolidayKind, Provider, get_filename, save};
+    use crate::holidays::{Hidden, HolidayEntry, HolidayKind, Provider, get_filename, save};
+    use crate::recurring::Rules;
     use chrono::{DateTime, Datelike, NaiveDate, TimeZone, Utc};
     use serial_test::serial;
     use std::cell::RefCell;
@@ -90,6 +520,7 @@ mod tests {
 
     struct RecordingEnv {
         now: DateTime<Utc>,
+        provider: Provider,
         holidays: RefCell<HashMap<i32, HM>>,
         output: RefCell<Vec<String>>,
         store: RefCell<HashMap<i32, HM>>,
@@ -99,6 +530,7 @@ mod tests {
         fn new(now: DateTime<Utc>) -> Self {
             Self {
                 now,
+                provider: Provider::default(),
                 holidays: RefCell::new(HashMap::new()),
                 output: RefCell::new(Vec::new()),
                 store: RefCell::new(HashMap::new()),
@@ -142,6 +574,22 @@ mod tests {
             Ok(())
         }
 
+        fn hidden(&self, _year: i32) -> Result<Hidden> {
+            Ok(Hidden::new())
+        }
+
+        fn save_hidden(&self, _year: i32, _hidden: &Hidden) -> Result<()> {
+            Ok(())
+        }
+
+        fn rules(&self) -> Result<Rules> {
+            Ok(Rules::new())
+        }
+
+        fn save_rules(&self, _rules: &Rules) -> Result<()> {
+            Ok(())
+        }
+
         fn print(&self, msg: &str) -> Result<()> {
             self.output.borrow_mut().push(msg.to_string());
             Ok(())
@@ -151,6 +599,14 @@ mod tests {
             self.output.borrow_mut().push(msg.to_string());
             Ok(())
         }
+
+        fn provider(&self) -> &Provider {
+            &self.provider
+        }
+
+        fn official_holidays(&self, _provider: &Provider, year: i32) -> Result<HM> {
+            self.holidays(year)
+        }
     }
 
     struct TempHome {
@@ -204,10 +660,17 @@ mod tests {
         let env = RecordingEnv::new(jan_first(2024)).with_holidays(2024, hm);
         let args = Args {
             country: None,
+            locale: None,
+            lang: None,
+            region: None,
+            include: Vec::new(),
+            person: None,
+            team_file: None,
             action: None,
         };
 
-        args.dispatch(&env).expect("dispatch succeeds");
+        args.dispatch(&env, &Config::default())
+            .expect("dispatch succeeds");
 
         let outputs = env.outputs();
         assert_eq!(outputs.len(), 1);
@@ -219,46 +682,344 @@ mod tests {
         let env = RecordingEnv::new(jan_first(2024));
         let args = Args {
             country: None,
+            locale: None,
+            lang: None,
+            region: None,
+            include: Vec::new(),
+            person: None,
+            team_file: None,
             action: Some(Commands::List {
+                all: false,
                 format: OutputFormat::Table,
+                columns: None,
+                template: None,
+                template_file: None,
+                tags: Vec::new(),
             }),
         };
 
-        args.dispatch(&env).expect("dispatch succeeds");
+        args.dispatch(&env, &Config::default())
+            .expect("dispatch succeeds");
 
         assert_eq!(env.outputs(), vec!["No holidays found".to_string()]);
     }
 
+    #[test]
+    fn dispatch_list_rejects_columns_for_non_tabular_formats() {
+        let env = RecordingEnv::new(jan_first(2024));
+        let args = Args {
+            country: None,
+            locale: None,
+            lang: None,
+            region: None,
+            include: Vec::new(),
+            person: None,
+            team_file: None,
+            action: Some(Commands::List {
+                all: false,
+                format: OutputFormat::Json,
+                columns: Some(vec![ListColumn::Date]),
+                template: None,
+                template_file: None,
+                tags: Vec::new(),
+            }),
+        };
+
+        let err = args
+            .dispatch(&env, &Config::default())
+            .expect_err("columns require csv or tsv");
+        assert!(matches!(err, CalError::Config(_)));
+    }
+
+    #[test]
+    fn dispatch_list_reads_template_file() {
+        let mut hm = HashMap::new();
+        hm.insert((1, 1), HolidayEntry::official("New Year's Day".to_string()));
+        let env = RecordingEnv::new(jan_first(2024)).with_holidays(2024, hm);
+        let mut path = std::env::temp_dir();
+        path.push(format!(
+            "cal2-template-{}",
+            SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .expect("time went backwards")
+                .as_nanos()
+        ));
+        fs::write(&path, "* {name} on {date:%d/%m}\n").expect("write template file");
+        let args = Args {
+            country: None,
+            locale: None,
+            lang: None,
+            region: None,
+            include: Vec::new(),
+            person: None,
+            team_file: None,
+            action: Some(Commands::List {
+                all: false,
+                format: OutputFormat::Table,
+                columns: None,
+                template: None,
+                template_file: Some(path.clone()),
+                tags: Vec::new(),
+            }),
+        };
+
+        args.dispatch(&env, &Config::default())
+            .expect("dispatch succeeds");
+
+        assert_eq!(env.outputs(), vec!["* New Year's Day on 01/01".to_string()]);
+        fs::remove_file(&path).expect("remove template file");
+    }
+
     #[test]
     fn dispatch_display_forwards_mode() {
         let env = RecordingEnv::new(jan_first(2024));
         let args = Args {
             country: None,
+            locale: None,
+            lang: None,
+            region: None,
+            include: Vec::new(),
+            person: None,
+            team_file: None,
             action: Some(Commands::Display {
+                format: DisplayFormat::Terminal,
+                paper: Paper::A4,
                 mode: Some(Mode::Year),
+                width: None,
+                quarter: None,
+                fiscal_start: None,
+                tags: Vec::new(),
             }),
         };
 
-        args.dispatch(&env).expect("dispatch succeeds");
+        args.dispatch(&env, &Config::default())
+            .expect("dispatch succeeds");
 
         let outputs = env.outputs();
         assert_eq!(outputs.len(), 1);
         assert!(outputs[0].contains("December 2024"));
     }
 
+    #[test]
+    fn dispatch_display_uses_configured_fiscal_year() {
+        let env = RecordingEnv::new(jan_first(2024));
+        let args = Args {
+            country: None,
+            locale: None,
+            lang: None,
+            region: None,
+            include: Vec::new(),
+            person: None,
+            team_file: None,
+            action: Some(Commands::Display {
+                format: DisplayFormat::Terminal,
+                paper: Paper::A4,
+                mode: None,
+                width: None,
+                quarter: Some(Quarter::Q1),
+                fiscal_start: None,
+                tags: Vec::new(),
+            }),
+        };
+        let config = Config {
+            fiscal_year_start: Some(4),
+            ..Config::default()
+        };
+
+        args.dispatch(&env, &config).expect("dispatch succeeds");
+
+        let outputs = env.outputs();
+        assert!(outputs[0].contains("April 2023"));
+        assert!(outputs[0].contains("June 2023"));
+    }
+
+    #[test]
+    fn dispatch_display_rejects_quarter_outside_q_mode() {
+        let env = RecordingEnv::new(jan_first(2024));
+        let args = Args {
+            country: None,
+            locale: None,
+            lang: None,
+            region: None,
+            include: Vec::new(),
+            person: None,
+            team_file: None,
+            action: Some(Commands::Display {
+                format: DisplayFormat::Terminal,
+                paper: Paper::A4,
+                mode: Some(Mode::Year),
+                width: None,
+                quarter: Some(Quarter::Q2),
+                fiscal_start: None,
+                tags: Vec::new(),
+            }),
+        };
+
+        let err = args
+            .dispatch(&env, &Config::default())
+            .expect_err("quarter requires q mode");
+        assert!(matches!(err, CalError::Config(_)));
+    }
+
+    #[test]
+    fn dispatch_prefers_cli_locale_over_config() {
+        let env = RecordingEnv::new(jan_first(2024));
+        let args = Args {
+            country: None,
+            locale: Some("de_DE".to_string()),
+            lang: None,
+            region: None,
+            include: Vec::new(),
+            person: None,
+            team_file: None,
+            action: None,
+        };
+        let config = Config {
+            locale: Some("es_AR".to_string()),
+            ..Config::default()
+        };
+
+        args.dispatch(&env, &config).expect("dispatch succeeds");
+
+        assert!(env.outputs()[0].contains("Januar 2024"));
+    }
+
+    #[test]
+    fn dispatch_compare_rejects_repeated_countries() {
+        let env = RecordingEnv::new(jan_first(2027));
+        let args = Args {
+            country: None,
+            locale: None,
+            lang: None,
+            region: None,
+            include: Vec::new(),
+            person: None,
+            team_file: None,
+            action: Some(Commands::Compare {
+                countries: vec!["DE".to_string(), "de".to_string()],
+                year: Some(2027),
+            }),
+        };
+
+        let err = args
+            .dispatch(&env, &Config::default())
+            .expect_err("duplicate countries are rejected");
+        assert!(matches!(err, CalError::Config(_)));
+        assert!(env.outputs().is_empty());
+    }
+
+    #[test]
+    fn parse_years_accepts_inclusive_ranges() {
+        assert_eq!(parse_years("2020..2030"), Ok(2020..=2030));
+        assert_eq!(parse_years("2020..=2022"), Ok(2020..=2022));
+        assert_eq!(parse_years("2027"), Ok(2027..=2027));
+        assert!(parse_years("2030..2020").is_err());
+        assert!(parse_years("soon..2020").is_err());
+    }
+
+    #[test]
+    fn dispatch_edit_parses_dates_and_type() {
+        let env = RecordingEnv::new(jan_first(2026));
+        let mut store = HashMap::new();
+        store.insert((24, 12), HolidayEntry::custom("Family dinner"));
+        env.store.borrow_mut().insert(2026, store);
+        let args = Args {
+            country: None,
+            locale: None,
+            lang: None,
+            region: None,
+            include: Vec::new(),
+            person: None,
+            team_file: None,
+            action: Some(Commands::Edit {
+                date: "24/12".to_string(),
+                description: None,
+                move_to: Some("2026-12-23".to_string()),
+                kind: Some(EntryType::Leave),
+            }),
+        };
+
+        args.dispatch(&env, &Config::default())
+            .expect("dispatch succeeds");
+
+        let stored = env.stored(2026).expect("store updated");
+        let entry = stored.get(&(23, 12)).expect("entry moved");
+        assert_eq!(entry.kind, HolidayKind::Leave);
+        assert!(!stored.contains_key(&(24, 12)));
+    }
+
+    #[test]
+    fn dispatch_edit_requires_a_change() {
+        let env = RecordingEnv::new(jan_first(2026));
+        let args = Args {
+            country: None,
+            locale: None,
+            lang: None,
+            region: None,
+            include: Vec::new(),
+            person: None,
+            team_file: None,
+            action: Some(Commands::Edit {
+                date: "24/12".to_string(),
+                description: None,
+                move_to: None,
+                kind: None,
+            }),
+        };
+
+        let err = args
+            .dispatch(&env, &Config::default())
+            .expect_err("edit without changes fails");
+        assert!(matches!(err, CalError::Config(_)));
+    }
+
+    #[test]
+    fn dispatch_rejects_unknown_locale() {
+        let env = RecordingEnv::new(jan_first(2024));
+        let args = Args {
+            country: None,
+            locale: None,
+            lang: None,
+            region: None,
+            include: Vec::new(),
+            person: None,
+            team_file: None,
+            action: None,
+        };
+        let config = Config {
+            locale: Some("klingon".to_string()),
+            ..Config::default()
+        };
+
+        let err = args
+            .dispatch(&env, &config)
+            .expect_err("unknown locale is rejected");
+        assert!(matches!(err, CalError::Config(_)));
+    }
+
     #[test]
     fn dispatch_add_forwards_to_actions() {
         let env = RecordingEnv::new(jan_first(2024));
         let args = Args {
             country: None,
+            locale: None,
+            lang: None,
+            region: None,
+            include: Vec::new(),
+            person: None,
+            team_file: None,
             action: Some(Commands::Add {
-                day: 1,
-                month: 5,
+                date: vec!["1".to_string(), "5".to_string()],
                 description: None,
+                repeat: None,
+                year: None,
+                half_day: false,
+                tags: Vec::new(),
             }),
         };
 
-        args.dispatch(&env).expect("dispatch succeeds");
+        args.dispatch(&env, &Config::default())
+            .expect("dispatch succeeds");
 
         let stored = env.stored(2024).expect("expected stored holidays");
         let entry = stored
@@ -268,19 +1029,130 @@ mod tests {
         assert!(entry.name.contains("Custom holiday"));
     }
 
+    #[test]
+    fn dispatch_add_rejects_dates_outside_the_calendar() {
+        let env = RecordingEnv::new(jan_first(2026));
+        for date in [vec!["31", "2"], vec!["0", "13"], vec!["2026-02-29"]] {
+            let args = Args {
+                country: None,
+                locale: None,
+                lang: None,
+                region: None,
+                include: Vec::new(),
+                person: None,
+                team_file: None,
+                action: Some(Commands::Add {
+                    date: date.iter().map(|part| part.to_string()).collect(),
+                    description: None,
+                    repeat: None,
+                    year: None,
+                    half_day: false,
+                    tags: Vec::new(),
+                }),
+            };
+
+            let err = args
+                .dispatch(&env, &Config::default())
+                .expect_err("impossible dates are rejected");
+            assert!(matches!(err, CalError::InvalidDate(_)), "{date:?}");
+        }
+        assert!(env.stored(2026).is_none());
+    }
+
+    #[test]
+    fn parse_date_args_accepts_both_syntaxes() {
+        let today = NaiveDate::from_ymd_opt(2026, 10, 16).expect("valid date");
+        let parse = |parts: &[&str]| {
+            let parts: Vec<String> = parts.iter().map(|part| part.to_string()).collect();
+            parse_date_args(&parts, today, None, None).expect("valid date")
+        };
+        assert_eq!(
+            parse(&["24", "12"]),
+            NaiveDate::from_ymd_opt(2026, 12, 24).expect("valid date")
+        );
+        assert_eq!(
+            parse(&["next", "friday"]),
+            NaiveDate::from_ymd_opt(2026, 10, 23).expect("valid date")
+        );
+        assert_eq!(
+            parse(&["tomorrow"]),
+            NaiveDate::from_ymd_opt(2026, 10, 17).expect("valid date")
+        );
+    }
+
+    #[test]
+    fn parse_tag_normalizes_and_rejects_bad_tags() {
+        assert_eq!(parse_tag("#OnCall").expect("valid tag"), "oncall");
+        assert_eq!(
+            parse_tag("team-backend").expect("valid tag"),
+            "team-backend"
+        );
+        assert!(parse_tag("").is_err());
+        assert!(parse_tag("two words").is_err());
+        assert!(parse_tag("a,b").is_err());
+    }
+
+    #[test]
+    fn parse_person_accepts_simple_names() {
+        assert_eq!(parse_person("Alice").expect("valid name"), "alice");
+        assert!(parse_person("alice smith").is_err());
+        assert!(parse_person("../bob").is_err());
+    }
+
+    #[test]
+    fn dispatch_team_rejects_person() {
+        let env = RecordingEnv::new(jan_first(2024));
+        let args = Args::parse_from(["cal2", "--person", "alice", "team"]);
+        let err = args
+            .dispatch(&env, &Config::default())
+            .expect_err("team with --person is rejected");
+        assert!(matches!(err, CalError::Config(_)));
+    }
+
+    #[test]
+    fn parse_date_args_targets_the_requested_year() {
+        let today = NaiveDate::from_ymd_opt(2026, 10, 16).expect("valid date");
+        let parse = |parts: &[&str]| {
+            let parts: Vec<String> = parts.iter().map(|part| part.to_string()).collect();
+            parse_date_args(&parts, today, None, Some(2027))
+        };
+        let expected = NaiveDate::from_ymd_opt(2027, 1, 4).expect("valid date");
+        assert_eq!(parse(&["4", "1"]).expect("day month"), expected);
+        assert_eq!(parse(&["04/01"]).expect("slash date"), expected);
+        assert_eq!(parse(&["2027-01-04"]).expect("iso date"), expected);
+        assert!(matches!(
+            parse(&["tomorrow"]),
+            Err(CalError::InvalidDate(_))
+        ));
+        assert!(matches!(
+            parse(&["2026-12-24"]),
+            Err(CalError::InvalidDate(_))
+        ));
+    }
+
     #[test]
     fn dispatch_add_forwards_description() {
         let env = RecordingEnv::new(jan_first(2024));
         let args = Args {
             country: None,
+            locale: None,
+            lang: None,
+            region: None,
+            include: Vec::new(),
+            person: None,
+            team_file: None,
             action: Some(Commands::Add {
-                day: 6,
-                month: 7,
+                date: vec!["06/07".to_string()],
                 description: Some("Independence Eve".to_string()),
+                repeat: None,
+                year: None,
+                half_day: false,
+                tags: Vec::new(),
             }),
         };
 
-        args.dispatch(&env).expect("dispatch succeeds");
+        args.dispatch(&env, &Config::default())
+            .expect("dispatch succeeds");
 
         let stored = env.stored(2024).expect("expected stored holidays");
         let entry = stored
@@ -309,6 +1181,12 @@ mod tests {
 
         let args = Args {
             country: None,
+            locale: None,
+            lang: None,
+            region: None,
+            include: Vec::new(),
+            person: None,
+            team_file: None,
             action: None,
         };
 
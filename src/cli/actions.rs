@@ -1,33 +1,168 @@
 use crate::HM;
-use crate::cli::{Mode, OutputFormat};
-use crate::display_month::DisplayMonth;
+use crate::calendar::{is_weekend, quarter_months, working_days, working_days_in_year};
+use crate::cli::{DisplayFormat, ListColumn, Mode, OutputFormat, Paper, Quarter};
+use crate::config::TagColors;
+use crate::display_month::{self, DisplayMonth};
+use crate::error::CalError;
 use crate::error::Result;
 use crate::holidays::{
-    HolidayEntry, HolidayKind, Provider, get_filename, get_holidays, load, save,
+    Hidden, HolidayEntry, HolidayKind, Provider, apply_hidden, get_custom_filename,
+    get_hidden_filename, get_holidays, get_person_filename, get_school_holidays, list_people, load,
+    load_custom, load_hidden, merge, save, save_hidden,
 };
-use chrono::{DateTime, Datelike, Utc};
+use crate::locale;
+use crate::recurring::{
+    Recurrence, Rule, Rules, apply_rules, get_person_rules_filename, get_rules_filename,
+    load_rules, next_after, save_rules,
+};
+use crate::render;
+use crate::team_file;
+use crate::template::{Template, Values};
+use chrono::{DateTime, Datelike, Locale, Month, NaiveDate, Utc, Weekday};
 use prettytable::{Cell, Row, Table, format};
-use std::collections::hash_map::Entry;
+use regex::RegexBuilder;
+use std::collections::{HashMap, hash_map::Entry};
 use std::io::{self, Write};
 use std::iter::zip;
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
+use std::thread;
+use terminal_size::Width;
 
 pub trait ActionEnvironment {
     fn now(&self) -> DateTime<Utc>;
     fn holidays(&self, year: i32) -> Result<HM>;
     fn load(&self, year: i32) -> Result<HM>;
     fn save(&self, year: i32, hm: &HM) -> Result<()>;
+    fn hidden(&self, year: i32) -> Result<Hidden>;
+    fn save_hidden(&self, year: i32, hidden: &Hidden) -> Result<()>;
+    fn rules(&self) -> Result<Rules>;
+    fn save_rules(&self, rules: &Rules) -> Result<()>;
     fn print(&self, msg: &str) -> Result<()>;
     fn println(&self, msg: &str) -> Result<()>;
+    fn provider(&self) -> &Provider;
+    fn official_holidays(&self, provider: &Provider, year: i32) -> Result<HM>;
+    fn holidays_range(&self, years: RangeInclusive<i32>) -> Result<Vec<(i32, HM)>> {
+        years.map(|year| Ok((year, self.holidays(year)?))).collect()
+    }
+    fn country_count(&self) -> usize {
+        1
+    }
+    fn terminal_width(&self) -> Option<usize> {
+        None
+    }
+    fn people(&self, _year: i32) -> Result<Vec<String>> {
+        Ok(Vec::new())
+    }
+    fn person_holidays(&self, _person: &str, _year: i32) -> Result<HM> {
+        Ok(HM::new())
+    }
+    fn team_days(&self, _year: i32) -> Result<HM> {
+        Ok(HM::new())
+    }
 }
 
-#[derive(Default)]
 pub struct RealEnvironment {
-    provider: Provider,
+    providers: Vec<Provider>,
+    language: Option<String>,
+    school_holidays: bool,
+    person: Option<String>,
+    team_file: Option<PathBuf>,
 }
 
 impl RealEnvironment {
     pub fn new(provider: Provider) -> Self {
-        Self { provider }
+        Self {
+            providers: vec![provider],
+            language: None,
+            school_holidays: false,
+            person: None,
+            team_file: None,
+        }
+    }
+
+    pub fn with_language(mut self, language: Option<String>) -> Self {
+        self.language = language;
+        self
+    }
+
+    pub fn with_school_holidays(mut self, school_holidays: bool) -> Self {
+        self.school_holidays = school_holidays;
+        self
+    }
+
+    pub fn with_overlay(mut self, providers: impl IntoIterator<Item = Provider>) -> Self {
+        self.providers.extend(providers);
+        self
+    }
+
+    pub fn with_person(mut self, person: Option<String>) -> Self {
+        self.person = person;
+        self
+    }
+
+    pub fn with_team_file(mut self, team_file: Option<PathBuf>) -> Self {
+        self.team_file = team_file;
+        self
+    }
+
+    fn entries_filename(&self, year: i32, provider: &Provider) -> String {
+        match &self.person {
+            Some(person) => get_person_filename(year, provider, person),
+            None => get_custom_filename(year, provider),
+        }
+    }
+
+    fn rules_filename(&self, provider: &Provider) -> String {
+        match &self.person {
+            Some(person) => get_person_rules_filename(provider, person),
+            None => get_rules_filename(provider),
+        }
+    }
+
+    fn provider_holidays(&self, provider: &Provider, year: i32) -> Result<HM> {
+        let region = provider.region();
+        let custom = load_custom(year, provider)?;
+        let mut hm: HM = get_holidays(year, provider)?
+            .into_iter()
+            .filter(|(_, entry)| region.is_none_or(|region| entry.applies_to(region)))
+            .collect();
+        apply_hidden(&mut hm, &load_hidden(&get_hidden_filename(year, provider))?);
+        for (key, entry) in custom {
+            hm.entry(key).or_insert(entry);
+        }
+        apply_rules(&mut hm, &load_rules(&get_rules_filename(provider))?, year);
+        if let Some(path) = &self.team_file {
+            for (key, entry) in team_file::load(path, year, None)? {
+                hm.entry(key).or_insert(entry);
+            }
+        }
+        if let Some(person) = &self.person {
+            for (key, entry) in self.person_holidays(person, year)? {
+                hm.entry(key).or_insert(entry);
+            }
+        }
+        if self.school_holidays {
+            for (key, entry) in get_school_holidays(year, provider)? {
+                hm.entry(key).or_insert(entry);
+            }
+        }
+        Ok(hm
+            .into_iter()
+            .map(|(key, entry)| match &self.language {
+                Some(language) => (key, entry.in_language(language)),
+                None => (key, entry),
+            })
+            .collect())
+    }
+
+    fn writable_provider(&self) -> Result<&Provider> {
+        match self.providers.as_slice() {
+            [provider] => Ok(provider),
+            _ => Err(CalError::Config(
+                "custom holidays can only be changed for a single --country".to_string(),
+            )),
+        }
     }
 }
 
@@ -37,18 +172,75 @@ impl ActionEnvironment for RealEnvironment {
     }
 
     fn holidays(&self, year: i32) -> Result<HM> {
-        get_holidays(year, &self.provider)
+        if let [provider] = self.providers.as_slice() {
+            return self.provider_holidays(provider, year);
+        }
+        let sets = self
+            .providers
+            .iter()
+            .map(|provider| {
+                Ok((
+                    provider.country_code().to_string(),
+                    self.provider_holidays(provider, year)?,
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(merge(sets))
+    }
+
+    fn holidays_range(&self, years: RangeInclusive<i32>) -> Result<Vec<(i32, HM)>> {
+        thread::scope(|scope| {
+            let handles: Vec<_> = years
+                .map(|year| scope.spawn(move || Ok((year, self.holidays(year)?))))
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| {
+                    handle.join().unwrap_or_else(|_| {
+                        Err(CalError::Cache("holiday fetch thread panicked".to_string()))
+                    })
+                })
+                .collect()
+        })
     }
 
     fn load(&self, year: i32) -> Result<HM> {
-        let fname = get_filename(year, &self.provider);
-        let cached = load(&fname)?;
+        let provider = self.writable_provider()?;
+        if let Some(path) = &self.team_file {
+            return team_file::load(path, year, self.person.as_deref());
+        }
+        if self.person.is_none() {
+            return load_custom(year, provider);
+        }
+        let cached = load(&self.entries_filename(year, provider))?;
         Ok(cached.unwrap_or_default())
     }
 
     fn save(&self, year: i32, hm: &HM) -> Result<()> {
-        let fname = get_filename(year, &self.provider);
-        save(&fname, hm)
+        let provider = self.writable_provider()?;
+        if let Some(path) = &self.team_file {
+            return team_file::save(path, year, self.person.as_deref(), hm);
+        }
+        save(&self.entries_filename(year, provider), hm)
+    }
+
+    fn hidden(&self, year: i32) -> Result<Hidden> {
+        load_hidden(&get_hidden_filename(year, self.writable_provider()?))
+    }
+
+    fn save_hidden(&self, year: i32, hidden: &Hidden) -> Result<()> {
+        save_hidden(
+            &get_hidden_filename(year, self.writable_provider()?),
+            hidden,
+        )
+    }
+
+    fn rules(&self) -> Result<Rules> {
+        load_rules(&self.rules_filename(self.writable_provider()?))
+    }
+
+    fn save_rules(&self, rules: &Rules) -> Result<()> {
+        save_rules(&self.rules_filename(self.writable_provider()?), rules)
     }
 
     fn print(&self, msg: &str) -> Result<()> {
@@ -64,26 +256,166 @@ impl ActionEnvironment for RealEnvironment {
         stdout.flush()?;
         Ok(())
     }
+
+    fn provider(&self) -> &Provider {
+        &self.providers[0]
+    }
+
+    fn official_holidays(&self, provider: &Provider, year: i32) -> Result<HM> {
+        let region = provider.region();
+        Ok(get_holidays(year, provider)?
+            .into_iter()
+            .filter(|(_, entry)| {
+                entry.kind == HolidayKind::Official
+                    && region.is_none_or(|region| entry.applies_to(region))
+            })
+            .map(|(key, entry)| match &self.language {
+                Some(language) => (key, entry.in_language(language)),
+                None => (key, entry),
+            })
+            .collect())
+    }
+
+    fn country_count(&self) -> usize {
+        self.providers.len()
+    }
+
+    fn terminal_width(&self) -> Option<usize> {
+        terminal_size::terminal_size().map(|(Width(w), _)| w as usize)
+    }
+
+    fn people(&self, year: i32) -> Result<Vec<String>> {
+        let slug = self.writable_provider()?.slug();
+        let mut people = list_people(&[
+            format!("hm-person-{slug}-{year}-"),
+            format!("hm-person-rules-{slug}-"),
+        ])?;
+        if let Some(path) = &self.team_file {
+            people.extend(team_file::people(path)?);
+            people.sort();
+            people.dedup();
+        }
+        Ok(people)
+    }
+
+    fn person_holidays(&self, person: &str, year: i32) -> Result<HM> {
+        let provider = self.writable_provider()?;
+        let mut hm = match &self.team_file {
+            Some(path) => team_file::load(path, year, Some(person))?,
+            None => load(&get_person_filename(year, provider, person))?.unwrap_or_default(),
+        };
+        let rules = load_rules(&get_person_rules_filename(provider, person))?;
+        apply_rules(&mut hm, &rules, year);
+        Ok(hm)
+    }
+
+    fn team_days(&self, year: i32) -> Result<HM> {
+        match &self.team_file {
+            Some(path) => team_file::load(path, year, None),
+            None => Ok(HM::new()),
+        }
+    }
 }
 
-pub fn display<E: ActionEnvironment>(env: &E, mode: Mode) -> Result<()> {
-    let now = env.now();
-    let hm = env.holidays(now.year())?;
-    let calendars: Vec<_> = match mode {
-        Mode::Q => {
-            let current = DisplayMonth::new(now.month(), now.year(), &hm)?;
-            vec![current.prev()?, current.clone(), current.next()?]
-        }
-        Mode::Month => vec![DisplayMonth::new(now.month(), now.year(), &hm)?],
-        Mode::Year => {
-            let mut rows = Vec::with_capacity(12);
-            for month in 1..=12 {
-                rows.push(DisplayMonth::new(month, now.year(), &hm)?);
-            }
-            rows
+const DEFAULT_COLUMNS: usize = 3;
+const MAX_COLUMNS: usize = 6;
+
+fn columns_for_width(width: Option<usize>) -> usize {
+    match width {
+        Some(width) => (width / display_month::WIDTH).clamp(1, MAX_COLUMNS),
+        None => DEFAULT_COLUMNS,
+    }
+}
+
+pub struct DisplayOptions {
+    pub mode: Mode,
+    pub format: DisplayFormat,
+    pub locale: Option<Locale>,
+    pub paper: Paper,
+    pub width: Option<usize>,
+    pub quarter: Option<Quarter>,
+    pub fiscal_start: u32,
+    pub tags: Vec<String>,
+    pub tag_colors: TagColors,
+}
+
+impl Default for DisplayOptions {
+    fn default() -> Self {
+        Self {
+            mode: Mode::Q,
+            format: DisplayFormat::default(),
+            locale: None,
+            paper: Paper::default(),
+            width: None,
+            quarter: None,
+            fiscal_start: 1,
+            tags: Vec::new(),
+            tag_colors: TagColors::new(),
         }
+    }
+}
+
+pub fn display<E: ActionEnvironment>(env: &E, options: &DisplayOptions) -> Result<()> {
+    let now = env.now();
+    let months: Vec<(u32, i32)> = match options.mode {
+        Mode::Q => quarter_months(
+            now.date_naive(),
+            options.fiscal_start,
+            options.quarter.map(Quarter::number),
+        ),
+        Mode::Month => vec![(now.month(), now.year())],
+        Mode::Year => (1..=12).map(|month| (month, now.year())).collect(),
     };
 
+    let mut years = HashMap::new();
+    for (_, year) in &months {
+        if !years.contains_key(year) {
+            let mut hm = env.holidays(*year)?;
+            retain_tagged(&mut hm, &options.tags);
+            years.insert(*year, hm);
+        }
+    }
+    let calendars = months
+        .iter()
+        .map(|(month, year)| {
+            DisplayMonth::new(*month, *year, &years[year]).map(|calendar| {
+                let calendar = calendar
+                    .with_today(now.date_naive())
+                    .with_countries(env.country_count())
+                    .with_tag_colors(options.tag_colors.clone());
+                match options.locale {
+                    Some(locale) => calendar.with_locale(locale),
+                    None => calendar,
+                }
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    match options.format {
+        DisplayFormat::Terminal => {
+            let columns = columns_for_width(options.width.or_else(|| env.terminal_width()));
+            env.print(&format_table(&calendars, columns))
+        }
+        DisplayFormat::Html => env.print(&render::html::render(&calendars)),
+        DisplayFormat::Json => env.println(&render::json::render(&calendars)?),
+        DisplayFormat::Svg => {
+            let page = render::page::layout(&calendars, options.paper.size());
+            env.print(&render::svg::render(&page))
+        }
+        DisplayFormat::Pdf => {
+            let page = render::page::layout(&calendars, options.paper.size());
+            env.print(&render::pdf::render(&page))
+        }
+    }
+}
+
+fn retain_tagged(hm: &mut HM, tags: &[String]) {
+    if !tags.is_empty() {
+        hm.retain(|_, entry| entry.has_any_tag(tags));
+    }
+}
+
+fn format_table(calendars: &[DisplayMonth], columns: usize) -> String {
     let mut table = Table::new();
     let format = format::FormatBuilder::new().padding(0, 0).build();
     table.set_format(format);
@@ -100,36 +432,117 @@ pub fn display<E: ActionEnvironment>(env: &E, mode: Mode) -> Result<()> {
         .map(|x| Cell::new(&x.format()))
         .collect::<Vec<_>>();
 
-    zip(headers.as_slice().chunks(3), bodies.as_slice().chunks(3)).for_each(|(header, body)| {
+    zip(
+        headers.as_slice().chunks(columns),
+        bodies.as_slice().chunks(columns),
+    )
+    .for_each(|(header, body)| {
         table.add_row(Row::new(header.to_vec()));
         table.add_row(Row::new(body.to_vec()));
     });
-    env.print(&table.to_string())
+    table.to_string()
+}
+
+pub struct ListOptions {
+    pub all: bool,
+    pub format: OutputFormat,
+    pub locale: Option<Locale>,
+    pub columns: Vec<ListColumn>,
+    pub template: Option<Template>,
+    pub tags: Vec<String>,
+}
+
+impl Default for ListOptions {
+    fn default() -> Self {
+        Self {
+            all: false,
+            format: OutputFormat::default(),
+            locale: None,
+            columns: ListColumn::DEFAULT.to_vec(),
+            template: None,
+            tags: Vec::new(),
+        }
+    }
 }
 
-pub fn list<E: ActionEnvironment>(env: &E, format: OutputFormat) -> Result<()> {
+pub fn list<E: ActionEnvironment>(env: &E, options: &ListOptions) -> Result<()> {
+    let uses_provider = options.columns.contains(&ListColumn::Provider)
+        || options
+            .template
+            .as_ref()
+            .is_some_and(Template::uses_provider);
+    if uses_provider && env.country_count() > 1 {
+        return Err(CalError::Config(
+            "the provider column needs a single --country; use the countries column instead"
+                .to_string(),
+        ));
+    }
     let now = env.now();
     let year = now.year();
-    let mut holidays: Vec<_> = env.holidays(year)?.into_iter().collect();
-
-    if holidays.is_empty() {
+    let mut hm = env.holidays(year)?;
+    retain_tagged(&mut hm, &options.tags);
+    let mut holidays: Vec<_> = hm
+        .into_iter()
+        .filter(|(_, entry)| options.all || entry.kind != HolidayKind::Worked)
+        .collect();
+
+    if holidays.is_empty() && options.template.is_none() && options.format == OutputFormat::Table {
         env.println("No holidays found")?;
         return Ok(());
     }
 
+    #[allow(clippy::unnecessary_sort_by)]
     holidays.sort_by(|a, b| (a.0.1, a.0.0).cmp(&(b.0.1, b.0.0)));
 
-    match format {
+    if let Some(template) = &options.template {
+        let provider = env.provider().slug();
+        let lines: Vec<String> = holidays
+            .into_iter()
+            .map(|((day, month), entry)| {
+                template.render(&Values {
+                    year,
+                    month,
+                    day,
+                    name: &entry.name,
+                    kind: entry.kind.label(),
+                    provider: &provider,
+                    locale: options.locale,
+                })
+            })
+            .collect::<Result<_>>()?;
+        if lines.is_empty() {
+            return Ok(());
+        }
+        return env.println(&lines.join("\n"));
+    }
+
+    let display_date =
+        |day: u32, month: u32| match (options.locale, NaiveDate::from_ymd_opt(year, month, day)) {
+            (Some(locale), Some(date)) => date.format_localized("%x", locale).to_string(),
+            _ => format!("{year}-{month:02}-{day:02}"),
+        };
+
+    match options.format {
         OutputFormat::Table => {
             let lines: Vec<String> = holidays
                 .into_iter()
                 .map(|((day, month), entry)| {
-                    let date = format!("{year}-{month:02}-{day:02}");
-                    let kind = match entry.kind {
-                        HolidayKind::Official => "official",
-                        HolidayKind::Custom => "custom",
-                    };
-                    format!("{date}  {} [{kind}]", entry.name)
+                    let date = display_date(day, month);
+                    let mut kind = entry.kind.label().to_string();
+                    if entry.half_day {
+                        kind.push_str(", half day");
+                    }
+                    if !entry.countries.is_empty() {
+                        kind = format!("{kind}; {}", entry.countries.join(", "));
+                    }
+                    let mut line = format!("{date}  {} [{kind}]", entry.name);
+                    if !entry.regions.is_empty() {
+                        line = format!("{line} ({})", entry.regions.join(", "));
+                    }
+                    for tag in &entry.tags {
+                        line = format!("{line} #{tag}");
+                    }
+                    line
                 })
                 .collect();
             env.println(&lines.join("\n"))
@@ -140,6 +553,14 @@ pub fn list<E: ActionEnvironment>(env: &E, format: OutputFormat) -> Result<()> {
                 date: String,
                 name: String,
                 kind: String,
+                #[serde(skip_serializing_if = "std::ops::Not::not")]
+                half_day: bool,
+                #[serde(skip_serializing_if = "Vec::is_empty")]
+                regions: Vec<String>,
+                #[serde(skip_serializing_if = "Vec::is_empty")]
+                countries: Vec<String>,
+                #[serde(skip_serializing_if = "Vec::is_empty")]
+                tags: Vec<String>,
             }
 
             let payload: Vec<Record> = holidays
@@ -147,11 +568,11 @@ pub fn list<E: ActionEnvironment>(env: &E, format: OutputFormat) -> Result<()> {
                 .map(|((day, month), entry)| Record {
                     date: format!("{year}-{month:02}-{day:02}"),
                     name: entry.name,
-                    kind: match entry.kind {
-                        HolidayKind::Official => "official",
-                        HolidayKind::Custom => "custom",
-                    }
-                    .to_string(),
+                    kind: entry.kind.label().to_string(),
+                    half_day: entry.half_day,
+                    regions: entry.regions,
+                    countries: entry.countries,
+                    tags: entry.tags,
                 })
                 .collect();
             let body = serde_json::to_string_pretty(&payload)?;
@@ -163,14 +584,11 @@ pub fn list<E: ActionEnvironment>(env: &E, format: OutputFormat) -> Result<()> {
             let mut width_name = "Name".len();
             let mut width_kind = "Kind".len();
             for ((day, month), entry) in holidays {
-                let date = format!("{year}-{month:02}-{day:02}");
-                let kind = match entry.kind {
-                    HolidayKind::Official => "official".to_string(),
-                    HolidayKind::Custom => "custom".to_string(),
-                };
-                width_date = width_date.max(date.len());
-                width_name = width_name.max(entry.name.len());
-                width_kind = width_kind.max(kind.len());
+                let date = display_date(day, month);
+                let kind = entry.kind.label().to_string();
+                width_date = width_date.max(date.chars().count());
+                width_name = width_name.max(entry.name.chars().count());
+                width_kind = width_kind.max(kind.chars().count());
                 records.push((date, entry.name, kind));
             }
 
@@ -203,128 +621,851 @@ pub fn list<E: ActionEnvironment>(env: &E, format: OutputFormat) -> Result<()> {
             }
             env.println(&rows.join("\n"))
         }
+        OutputFormat::Csv | OutputFormat::Tsv => {
+            let separator = if options.format == OutputFormat::Csv {
+                ","
+            } else {
+                "\t"
+            };
+            let escape = |field: &str| {
+                if options.format == OutputFormat::Csv {
+                    escape_csv(field)
+                } else {
+                    escape_tsv(field)
+                }
+            };
+            let provider = env.provider().slug();
+            let mut rows = Vec::with_capacity(holidays.len() + 1);
+            rows.push(
+                options
+                    .columns
+                    .iter()
+                    .map(|column| column.header())
+                    .collect::<Vec<_>>()
+                    .join(separator),
+            );
+            for ((day, month), entry) in holidays {
+                let date = NaiveDate::from_ymd_opt(year, month, day);
+                let fields: Vec<String> = options
+                    .columns
+                    .iter()
+                    .map(|column| match column {
+                        ListColumn::Date => format!("{year}-{month:02}-{day:02}"),
+                        ListColumn::Weekday => {
+                            date.map(|d| d.format("%a").to_string()).unwrap_or_default()
+                        }
+                        ListColumn::Name => escape(&entry.name),
+                        ListColumn::Kind => entry.kind.label().to_string(),
+                        ListColumn::Provider => provider.clone(),
+                        ListColumn::Regions => entry.regions.join(" "),
+                        ListColumn::Countries => entry.countries.join(" "),
+                        ListColumn::Tags => entry.tags.join(" "),
+                    })
+                    .collect();
+                rows.push(fields.join(separator));
+            }
+            env.println(&rows.join("\n"))
+        }
     }
 }
 
-pub fn add<E: ActionEnvironment>(
-    env: &E,
-    day: u32,
-    month: u32,
-    description: Option<String>,
-) -> Result<()> {
-    let now = env.now();
-    let mut hm = env.load(now.year())?;
-    if let Entry::Vacant(v) = hm.entry((day, month)) {
-        let name = description
-            .and_then(|d| {
-                let trimmed = d.trim();
-                if trimmed.is_empty() {
-                    None
-                } else {
-                    Some(trimmed.to_string())
-                }
-            })
-            .unwrap_or_else(|| format!("Custom holiday ({day:02}/{month:02})"));
-        v.insert(HolidayEntry::custom(name));
+fn escape_csv(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
     }
-    env.save(now.year(), &hm)?;
-    env.println("OK")
 }
 
-pub fn delete<E: ActionEnvironment>(env: &E, day: u32, month: u32) -> Result<()> {
-    let now = env.now();
-    let mut hm = env.load(now.year())?;
-    hm.remove(&(day, month));
-    env.save(now.year(), &hm)?;
-    env.println("OK")
+fn escape_tsv(field: &str) -> String {
+    field.replace(['\t', '\n', '\r'], " ")
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::cli::Mode;
-    use crate::holidays::{HolidayEntry, Provider, get_filename};
-    use chrono::{NaiveDate, TimeZone};
-    use serial_test::serial;
-    use std::cell::RefCell;
-    use std::collections::HashMap;
-    use std::fs;
-    use std::path::{Path, PathBuf};
-    use std::time::SystemTime;
+fn description_name(description: Option<String>) -> Option<String> {
+    description.and_then(|d| {
+        let trimmed = d.trim();
+        if trimmed.is_empty() {
+            None
+        } else {
+            Some(trimmed.to_string())
+        }
+    })
+}
 
-    struct TestEnvironment {
-        now: DateTime<Utc>,
-        holidays: RefCell<HashMap<i32, HM>>,
-        store: RefCell<HashMap<i32, HM>>,
-        output: RefCell<Vec<String>>,
-    }
+#[derive(Default)]
+pub struct AddOptions {
+    pub description: Option<String>,
+    pub half_day: bool,
+    pub tags: Vec<String>,
+}
 
-    impl TestEnvironment {
-        fn new(date: DateTime<Utc>) -> Self {
-            Self {
-                now: date,
-                holidays: RefCell::new(HashMap::new()),
-                store: RefCell::new(HashMap::new()),
-                output: RefCell::new(Vec::new()),
-            }
-        }
+/// The official holiday on `date`. Custom entries are stored apart from the
+/// provider's holidays, so edits have to look both up.
+fn official_on<E: ActionEnvironment>(env: &E, date: NaiveDate) -> Result<Option<HolidayEntry>> {
+    let mut official = env.official_holidays(env.provider(), date.year())?;
+    Ok(official.remove(&(date.day(), date.month())))
+}
 
-        fn with_holidays(self, year: i32, hm: HM) -> Self {
-            self.holidays.borrow_mut().insert(year, hm);
-            self
+pub fn add<E: ActionEnvironment>(env: &E, date: NaiveDate, options: &AddOptions) -> Result<()> {
+    let (day, month) = (date.day(), date.month());
+    let mut hm = env.load(date.year())?;
+    if let Some(official) = official_on(env, date)? {
+        return Err(CalError::Entry(format!(
+            "{date} is an official holiday ({})",
+            official.name
+        )));
+    }
+    match hm.entry((day, month)) {
+        Entry::Occupied(existing) => {
+            return Err(CalError::Entry(format!(
+                "{date} already has {} ({}); use cal2 edit to change it",
+                existing.get().name,
+                existing.get().kind.label()
+            )));
         }
-
-        fn with_store(self, year: i32, hm: HM) -> Self {
-            self.store.borrow_mut().insert(year, hm);
-            self
+        Entry::Vacant(v) => {
+            let name = description_name(options.description.clone())
+                .unwrap_or_else(|| format!("Custom holiday ({day:02}/{month:02})"));
+            v.insert(HolidayEntry {
+                half_day: options.half_day,
+                tags: options.tags.clone(),
+                ..HolidayEntry::custom(name)
+            });
         }
+    }
+    env.save(date.year(), &hm)?;
+    env.println("OK")
+}
 
-        fn outputs(&self) -> Vec<String> {
-            self.output.borrow().clone()
-        }
+pub fn add_rule<E: ActionEnvironment>(
+    env: &E,
+    date: NaiveDate,
+    description: Option<String>,
+    recurrence: Recurrence,
+) -> Result<()> {
+    let rule = Rule::new(
+        date,
+        recurrence,
+        description_name(description).unwrap_or_else(|| "Recurring day off".to_string()),
+    );
+    let mut rules = env.rules()?;
+    if rules.contains(&rule) {
+        return Err(CalError::Entry(format!(
+            "{} {} is already recorded",
+            rule.name,
+            rule.describe()
+        )));
+    }
+    let message = format!("OK: {} {}", rule.name, rule.describe());
+    rules.push(rule);
+    env.save_rules(&rules)?;
+    env.println(&message)
+}
 
-        fn stored(&self, year: i32) -> Option<HM> {
-            self.store.borrow().get(&year).cloned()
+pub fn rules<E: ActionEnvironment>(env: &E, remove: Option<usize>) -> Result<()> {
+    let mut rules = env.rules()?;
+    if let Some(number) = remove {
+        if number == 0 || number > rules.len() {
+            return Err(CalError::Entry(format!(
+                "there is no recurring entry #{number}"
+            )));
         }
+        rules.remove(number - 1);
+        env.save_rules(&rules)?;
+        return env.println("OK");
     }
+    if rules.is_empty() {
+        return env.println("No recurring entries.");
+    }
+    let today = env.now().date_naive();
+    for (number, rule) in rules.iter().enumerate() {
+        let next = match next_after(rule, today) {
+            Some(date) => format!("next {date}"),
+            None => "no upcoming date".to_string(),
+        };
+        env.println(&format!(
+            "{:>2}. {} - {} since {} ({next})",
+            number + 1,
+            rule.name,
+            rule.describe(),
+            rule.start
+        ))?;
+    }
+    Ok(())
+}
 
-    impl ActionEnvironment for TestEnvironment {
-        fn now(&self) -> DateTime<Utc> {
-            self.now
-        }
+pub fn compare<E: ActionEnvironment>(
+    env: &E,
+    providers: &[Provider],
+    year: Option<i32>,
+) -> Result<()> {
+    let year = year.unwrap_or_else(|| env.now().year());
+    let sets = providers
+        .iter()
+        .map(|provider| {
+            let hm = env.official_holidays(provider, year)?;
+            Ok((provider.country_code().to_string(), hm))
+        })
+        .collect::<Result<Vec<_>>>()?;
 
-        fn holidays(&self, year: i32) -> Result<HM> {
-            Ok(self
-                .holidays
-                .borrow()
-                .get(&year)
-                .cloned()
-                .unwrap_or_default())
+    let width = sets
+        .iter()
+        .map(|(code, _)| code.len())
+        .max()
+        .unwrap_or_default()
+        .max(6);
+    let row = |label: &str, values: Vec<String>| {
+        let values: Vec<String> = values
+            .iter()
+            .map(|value| format!("{value:>width$}"))
+            .collect();
+        format!("{label:<14}{}", values.join("  "))
+            .trim_end()
+            .to_string()
+    };
+    let mut lines = vec![
+        format!("Comparison for {year}"),
+        row("", sets.iter().map(|(code, _)| code.clone()).collect()),
+        row(
+            "Holidays",
+            sets.iter().map(|(_, hm)| hm.len().to_string()).collect(),
+        ),
+        row(
+            "Working days",
+            sets.iter()
+                .map(|(_, hm)| working_days_in_year(year, hm).to_string())
+                .collect(),
+        ),
+    ];
+
+    let codes: Vec<String> = sets.iter().map(|(code, _)| code.clone()).collect();
+    let mut merged: Vec<_> = merge(sets).into_iter().collect();
+    merged.sort_by_key(|((day, month), _)| (*month, *day));
+    let section = |lines: &mut Vec<String>, title: String, entries: Vec<String>| {
+        lines.push(String::new());
+        lines.push(title);
+        if entries.is_empty() {
+            lines.push("  none".to_string());
         }
+        lines.extend(entries);
+    };
+    let describe = |((day, month), entry): &((u32, u32), HolidayEntry)| {
+        format!("  {year}-{month:02}-{day:02}  {}", entry.name)
+    };
 
-        fn load(&self, year: i32) -> Result<HM> {
-            Ok(self.store.borrow().get(&year).cloned().unwrap_or_default())
-        }
+    for code in &codes {
+        let entries = merged
+            .iter()
+            .filter(|(_, entry)| entry.countries == [code.as_str()])
+            .map(describe)
+            .collect();
+        section(&mut lines, format!("Only in {code}:"), entries);
+    }
+    let shared = merged
+        .iter()
+        .filter(|(_, entry)| entry.countries.len() == codes.len())
+        .map(describe)
+        .collect();
+    section(&mut lines, "Shared:".to_string(), shared);
+    if codes.len() > 2 {
+        let partial = merged
+            .iter()
+            .filter(|(_, entry)| (2..codes.len()).contains(&entry.countries.len()))
+            .map(|holiday| format!("{} ({})", describe(holiday), holiday.1.countries.join(", ")))
+            .collect();
+        section(&mut lines, "Partly shared:".to_string(), partial);
+    }
 
-        fn save(&self, year: i32, hm: &HM) -> Result<()> {
-            self.store.borrow_mut().insert(year, hm.clone());
-            Ok(())
-        }
+    env.println(&lines.join("\n"))
+}
 
-        fn print(&self, msg: &str) -> Result<()> {
-            self.output.borrow_mut().push(msg.to_string());
-            Ok(())
-        }
+pub fn stats<E: ActionEnvironment>(
+    env: &E,
+    year: Option<i32>,
+    locale: Option<Locale>,
+) -> Result<()> {
+    let year = year.unwrap_or_else(|| env.now().year());
+    let mut hm = env.holidays(year)?;
+    hm.retain(|_, entry| entry.kind != HolidayKind::Worked);
+    let dates: Vec<(NaiveDate, &HolidayEntry)> = hm
+        .iter()
+        .filter_map(|((day, month), entry)| {
+            NaiveDate::from_ymd_opt(year, *month, *day).map(|date| (date, entry))
+        })
+        .collect();
 
-        fn println(&self, msg: &str) -> Result<()> {
-            self.output.borrow_mut().push(format!("{msg}\n"));
-            Ok(())
+    // Custom and leave days are balances, so a half day counts as half.
+    let weight = |entry: &HolidayEntry| match entry.kind {
+        HolidayKind::Custom | HolidayKind::Leave => entry.day_off(),
+        _ => 1.0,
+    };
+    let count = |keep: &dyn Fn(NaiveDate) -> bool| -> f64 {
+        dates
+            .iter()
+            .filter(|(date, _)| keep(*date))
+            .fold(0.0, |sum, (_, entry)| sum + weight(entry))
+    };
+
+    let mut kinds: Vec<(&str, f64)> = Vec::new();
+    for (_, entry) in &dates {
+        match kinds
+            .iter_mut()
+            .find(|(label, _)| *label == entry.kind.label())
+        {
+            Some((_, count)) => *count += weight(entry),
+            None => kinds.push((entry.kind.label(), weight(entry))),
         }
     }
+    kinds.sort_by_key(|(label, _)| *label);
+    let total = count(&|_| true);
+    let weekends = count(&is_weekend);
+
+    let mut lines = vec![format!("Holidays in {year}: {total}")];
+    lines.extend(
+        kinds
+            .iter()
+            .map(|(label, count)| format!("  {label}: {count}")),
+    );
+    lines.push(format!("On weekdays: {}", total - weekends));
+    lines.push(format!("On weekends: {weekends}"));
+
+    lines.push(String::new());
+    lines.push(format!(
+        "{:<10}{:>10}{:>14}",
+        "Month", "Holidays", "Working days"
+    ));
+    for month in 1..=12 {
+        let name = match locale {
+            Some(locale) => locale::month_name(month, locale).unwrap_or_default(),
+            None => Month::try_from(month as u8)
+                .map(|m| m.name().to_string())
+                .unwrap_or_default(),
+        };
+        let count = count(&|date| date.month() == month);
+        lines.push(format!(
+            "{name:<10}{count:>10}{:>14}",
+            working_days(year, month, &hm)
+        ));
+    }
+    lines.push(format!(
+        "{:<10}{total:>10}{:>14}",
+        "Total",
+        working_days_in_year(year, &hm)
+    ));
+
+    lines.push(String::new());
+    lines.push(format!("{:<10}{:>10}", "Weekday", "Holidays"));
+    let mut weekday = Weekday::Mon;
+    for _ in 0..7 {
+        let count = count(&|date| date.weekday() == weekday);
+        let name = match locale {
+            Some(locale) => locale::weekday_name(weekday, locale),
+            None => weekday.to_string(),
+        };
+        lines.push(format!("{name:<10}{count:>10}"));
+        weekday = weekday.succ();
+    }
 
-    struct TempHome {
-        previous: Option<String>,
-        path: PathBuf,
+    env.println(&lines.join("\n"))
+}
+
+pub struct SearchOptions {
+    pub pattern: String,
+    pub years: Option<RangeInclusive<i32>>,
+    pub regex: bool,
+}
+
+const MAX_SEARCH_YEARS: usize = 50;
+
+pub fn search<E: ActionEnvironment>(env: &E, options: &SearchOptions) -> Result<()> {
+    let years = options.years.clone().unwrap_or_else(|| {
+        let year = env.now().year();
+        year..=year
+    });
+    if years.clone().count() > MAX_SEARCH_YEARS {
+        return Err(CalError::Config(format!(
+            "--years can span at most {MAX_SEARCH_YEARS} years"
+        )));
+    }
+    let matcher = if options.regex {
+        Some(
+            RegexBuilder::new(&options.pattern)
+                .case_insensitive(true)
+                .build()
+                .map_err(|err| CalError::Config(format!("invalid --regex pattern: {err}")))?,
+        )
+    } else {
+        None
+    };
+    let needle = options.pattern.to_lowercase();
+    let matches = |text: &str| match &matcher {
+        Some(regex) => regex.is_match(text),
+        None => text.to_lowercase().contains(&needle),
+    };
+
+    let mut found = Vec::new();
+    for (year, hm) in env.holidays_range(years)? {
+        for ((day, month), entry) in hm {
+            if matches(&entry.name) || entry.translations.values().any(|text| matches(text)) {
+                found.push((year, month, day, entry));
+            }
+        }
+    }
+
+    if found.is_empty() {
+        return env.println(&format!("No holidays matching {:?}", options.pattern));
+    }
+
+    found.sort_by_key(|(year, month, day, _)| (*year, *month, *day));
+    let lines: Vec<String> = found
+        .into_iter()
+        .map(|(year, month, day, entry)| {
+            format!(
+                "{year}-{month:02}-{day:02}  {} [{}]",
+                entry.name,
+                entry.kind.label()
+            )
+        })
+        .collect();
+    env.println(&lines.join("\n"))
+}
+
+#[derive(Default)]
+pub struct EditOptions {
+    pub description: Option<String>,
+    pub move_to: Option<NaiveDate>,
+    pub kind: Option<HolidayKind>,
+}
+
+pub fn edit<E: ActionEnvironment>(env: &E, date: NaiveDate, options: &EditOptions) -> Result<()> {
+    let key = (date.day(), date.month());
+    let mut hm = env.load(date.year())?;
+    let mut entry = match hm.get(&key) {
+        None => {
+            return Err(CalError::Entry(match official_on(env, date)? {
+                Some(official) => format!(
+                    "{date} is an official holiday ({}); only custom and leave entries can be edited",
+                    official.name
+                ),
+                None => format!("no custom entry on {date}"),
+            }));
+        }
+        Some(entry) if !matches!(entry.kind, HolidayKind::Custom | HolidayKind::Leave) => {
+            return Err(CalError::Entry(format!(
+                "{date} is an {} holiday ({}); only custom and leave entries can be edited",
+                entry.kind.label(),
+                entry.name
+            )));
+        }
+        Some(entry) => entry.clone(),
+    };
+
+    if let Some(description) = &options.description {
+        let trimmed = description.trim();
+        if trimmed.is_empty() {
+            return Err(CalError::Entry("--description cannot be empty".to_string()));
+        }
+        entry.name = trimmed.to_string();
+    }
+    if let Some(kind) = options.kind {
+        entry.kind = kind;
+    }
+
+    let target = options.move_to.unwrap_or(date);
+    let target_key = (target.day(), target.month());
+    if target == date {
+        hm.insert(key, entry);
+        env.save(date.year(), &hm)?;
+        return env.println("OK");
+    }
+
+    let mut target_hm = if target.year() == date.year() {
+        None
+    } else {
+        Some(env.load(target.year())?)
+    };
+    let occupied = match target_hm.as_ref().unwrap_or(&hm).get(&target_key) {
+        Some(existing) => Some(existing.clone()),
+        None => official_on(env, target)?,
+    };
+    if let Some(existing) = occupied {
+        return Err(CalError::Entry(format!(
+            "{target} already has {} ({})",
+            existing.name,
+            existing.kind.label()
+        )));
+    }
+
+    hm.remove(&key);
+    match &mut target_hm {
+        Some(target_hm) => {
+            target_hm.insert(target_key, entry);
+            env.save(target.year(), target_hm)?;
+        }
+        None => {
+            hm.insert(target_key, entry);
+        }
+    }
+    env.save(date.year(), &hm)?;
+    env.println("OK")
+}
+
+pub fn team<E: ActionEnvironment>(
+    env: &E,
+    month: Option<u32>,
+    year: Option<i32>,
+    locale: Option<Locale>,
+) -> Result<()> {
+    let now = env.now();
+    let year = year.unwrap_or_else(|| now.year());
+    let month = month.unwrap_or_else(|| now.month());
+    let first = NaiveDate::from_ymd_opt(year, month, 1)
+        .ok_or_else(|| CalError::InvalidDate(format!("invalid month {month}")))?;
+    let people = env.people(year)?;
+    if people.is_empty() {
+        return env.println("No people found; add entries with cal2 --person <NAME> add");
+    }
+
+    // Only days the whole team has off; anyone's personal entries, including
+    // the invoking user's, show up in their own row instead.
+    let mut shared = env.official_holidays(env.provider(), year)?;
+    for (key, entry) in env.team_days(year)? {
+        shared.entry(key).or_insert(entry);
+    }
+    let days: Vec<NaiveDate> = first
+        .iter_days()
+        .take_while(|date| date.month() == month)
+        .collect();
+    let closed = |date: &NaiveDate| {
+        is_weekend(*date)
+            || shared
+                .get(&(date.day(), month))
+                .is_some_and(|entry| entry.kind.is_day_off())
+    };
+    let width = people
+        .iter()
+        .map(|person| person.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("Out".len());
+
+    let name = match locale {
+        Some(locale) => locale::month_name(month, locale).unwrap_or_default(),
+        None => Month::try_from(month as u8)
+            .map(|m| m.name().to_string())
+            .unwrap_or_default(),
+    };
+    let mut lines = vec![format!("Team {name} {year}")];
+    let header = |cell: &dyn Fn(&NaiveDate) -> String| {
+        let cells: String = days
+            .iter()
+            .map(|date| format!("{:>3}", cell(date)))
+            .collect();
+        format!("{:width$}{cells}", "")
+    };
+    lines.push(header(&|date| {
+        let name = match locale {
+            Some(locale) => locale::weekday_name(date.weekday(), locale),
+            None => date.weekday().to_string(),
+        };
+        name.chars().take(2).collect()
+    }));
+    lines.push(header(&|date| date.day().to_string()));
+
+    let mut out = vec![0.0; days.len()];
+    for person in &people {
+        let entries = env.person_holidays(person, year)?;
+        let cells: String = days
+            .iter()
+            .zip(out.iter_mut())
+            .map(|(date, out)| {
+                let symbol = if is_weekend(*date) {
+                    "-"
+                } else if closed(date) {
+                    "H"
+                } else {
+                    match entries.get(&(date.day(), month)).map(HolidayEntry::day_off) {
+                        Some(share) if share >= 1.0 => {
+                            *out += share;
+                            "x"
+                        }
+                        Some(share) if share > 0.0 => {
+                            *out += share;
+                            "/"
+                        }
+                        _ => ".",
+                    }
+                };
+                format!("{symbol:>3}")
+            })
+            .collect();
+        lines.push(format!("{person:width$}{cells}"));
+    }
+    let counts: String = days
+        .iter()
+        .zip(&out)
+        .map(|(date, count)| {
+            if closed(date) {
+                format!("{:>3}", "")
+            } else if count.fract() == 0.0 {
+                format!("{count:>3}")
+            } else {
+                // A half day keeps the column three characters wide.
+                let whole = count.trunc();
+                let whole = if whole == 0.0 {
+                    String::new()
+                } else {
+                    whole.to_string()
+                };
+                format!("{:>3}", format!("{whole}½"))
+            }
+        })
+        .collect();
+    lines.push(format!("{:width$}{counts}", "Out").trim_end().to_string());
+    lines.push(String::new());
+    lines.push("x day off, / half day, H holiday, - weekend".to_string());
+    env.println(&lines.join("\n"))
+}
+
+pub fn hide<E: ActionEnvironment>(env: &E, date: NaiveDate) -> Result<()> {
+    let key = (date.day(), date.month());
+    match env.holidays(date.year())?.get(&key) {
+        Some(entry) if entry.kind == HolidayKind::Official => {}
+        Some(entry) if entry.kind == HolidayKind::Worked => {
+            return Err(CalError::Entry(format!("{date} is already hidden")));
+        }
+        _ => {
+            return Err(CalError::Entry(format!(
+                "no official holiday on {date}; use cal2 delete for custom entries"
+            )));
+        }
+    }
+    let mut hidden = env.hidden(date.year())?;
+    hidden.insert(key);
+    env.save_hidden(date.year(), &hidden)?;
+    env.println("OK")
+}
+
+pub fn unhide<E: ActionEnvironment>(env: &E, date: NaiveDate) -> Result<()> {
+    let mut hidden = env.hidden(date.year())?;
+    if !hidden.remove(&(date.day(), date.month())) {
+        return Err(CalError::Entry(format!("{date} is not hidden")));
+    }
+    env.save_hidden(date.year(), &hidden)?;
+    env.println("OK")
+}
+
+pub fn delete<E: ActionEnvironment>(env: &E, date: NaiveDate) -> Result<()> {
+    let key = (date.day(), date.month());
+    let mut hm = env.load(date.year())?;
+    if let Some(official) = official_on(env, date)? {
+        return Err(CalError::Entry(format!(
+            "{} is an official holiday; use cal2 hide to work on it",
+            official.name
+        )));
+    }
+    if hm.remove(&key).is_none() {
+        return Err(CalError::Entry(format!("no custom entry on {date}")));
+    }
+    env.save(date.year(), &hm)?;
+    env.println("OK")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::Mode;
+    use crate::holidays::{HolidayEntry, HolidayKind, Provider, get_filename};
+    use chrono::{NaiveDate, TimeZone};
+    use serial_test::serial;
+    use std::cell::RefCell;
+    use std::collections::{BTreeMap, HashMap};
+    use std::fs;
+    use std::path::{Path, PathBuf};
+    use std::time::SystemTime;
+
+    struct TestEnvironment {
+        now: DateTime<Utc>,
+        provider: Provider,
+        countries: usize,
+        holidays: RefCell<HashMap<i32, HM>>,
+        by_country: RefCell<HashMap<(String, i32), HM>>,
+        hidden: RefCell<HashMap<i32, Hidden>>,
+        rules: RefCell<Rules>,
+        people: RefCell<BTreeMap<String, HashMap<i32, HM>>>,
+        store: RefCell<HashMap<i32, HM>>,
+        team_days: RefCell<HashMap<i32, HM>>,
+        output: RefCell<Vec<String>>,
+    }
+
+    impl TestEnvironment {
+        fn new(date: DateTime<Utc>) -> Self {
+            Self {
+                now: date,
+                provider: Provider::default(),
+                countries: 1,
+                holidays: RefCell::new(HashMap::new()),
+                by_country: RefCell::new(HashMap::new()),
+                hidden: RefCell::new(HashMap::new()),
+                rules: RefCell::new(Rules::new()),
+                people: RefCell::new(BTreeMap::new()),
+                store: RefCell::new(HashMap::new()),
+                team_days: RefCell::new(HashMap::new()),
+                output: RefCell::new(Vec::new()),
+            }
+        }
+
+        fn with_country_holidays(self, code: &str, year: i32, hm: HM) -> Self {
+            self.by_country
+                .borrow_mut()
+                .insert((code.to_string(), year), hm);
+            self
+        }
+
+        fn with_countries(mut self, countries: usize) -> Self {
+            self.countries = countries;
+            self
+        }
+
+        fn with_holidays(self, year: i32, hm: HM) -> Self {
+            self.holidays.borrow_mut().insert(year, hm);
+            self
+        }
+
+        fn with_person(self, person: &str, year: i32, hm: HM) -> Self {
+            self.people
+                .borrow_mut()
+                .entry(person.to_string())
+                .or_default()
+                .insert(year, hm);
+            self
+        }
+
+        fn with_team_days(self, year: i32, hm: HM) -> Self {
+            self.team_days.borrow_mut().insert(year, hm);
+            self
+        }
+
+        fn with_store(self, year: i32, hm: HM) -> Self {
+            self.store.borrow_mut().insert(year, hm);
+            self
+        }
+
+        fn outputs(&self) -> Vec<String> {
+            self.output.borrow().clone()
+        }
+
+        fn stored(&self, year: i32) -> Option<HM> {
+            self.store.borrow().get(&year).cloned()
+        }
+    }
+
+    impl ActionEnvironment for TestEnvironment {
+        fn now(&self) -> DateTime<Utc> {
+            self.now
+        }
+
+        fn holidays(&self, year: i32) -> Result<HM> {
+            Ok(self
+                .holidays
+                .borrow()
+                .get(&year)
+                .cloned()
+                .unwrap_or_default())
+        }
+
+        fn load(&self, year: i32) -> Result<HM> {
+            let mut hm = self.store.borrow().get(&year).cloned().unwrap_or_default();
+            hm.retain(|_, entry| matches!(entry.kind, HolidayKind::Custom | HolidayKind::Leave));
+            Ok(hm)
+        }
+
+        fn save(&self, year: i32, hm: &HM) -> Result<()> {
+            self.store.borrow_mut().insert(year, hm.clone());
+            Ok(())
+        }
+
+        fn hidden(&self, year: i32) -> Result<Hidden> {
+            Ok(self.hidden.borrow().get(&year).cloned().unwrap_or_default())
+        }
+
+        fn save_hidden(&self, year: i32, hidden: &Hidden) -> Result<()> {
+            self.hidden.borrow_mut().insert(year, hidden.clone());
+            Ok(())
+        }
+
+        fn rules(&self) -> Result<Rules> {
+            Ok(self.rules.borrow().clone())
+        }
+
+        fn save_rules(&self, rules: &Rules) -> Result<()> {
+            *self.rules.borrow_mut() = rules.clone();
+            Ok(())
+        }
+
+        fn print(&self, msg: &str) -> Result<()> {
+            self.output.borrow_mut().push(msg.to_string());
+            Ok(())
+        }
+
+        fn println(&self, msg: &str) -> Result<()> {
+            self.output.borrow_mut().push(format!("{msg}\n"));
+            Ok(())
+        }
+
+        fn provider(&self) -> &Provider {
+            &self.provider
+        }
+
+        fn official_holidays(&self, provider: &Provider, year: i32) -> Result<HM> {
+            let mut hm = self
+                .by_country
+                .borrow()
+                .get(&(provider.country_code().to_string(), year))
+                .or(self.holidays.borrow().get(&year))
+                .cloned()
+                .unwrap_or_default();
+            hm.retain(|_, entry| entry.kind == HolidayKind::Official);
+            Ok(hm)
+        }
+
+        fn country_count(&self) -> usize {
+            self.countries
+        }
+
+        fn people(&self, year: i32) -> Result<Vec<String>> {
+            Ok(self
+                .people
+                .borrow()
+                .iter()
+                .filter(|(_, years)| years.contains_key(&year))
+                .map(|(person, _)| person.clone())
+                .collect())
+        }
+
+        fn person_holidays(&self, person: &str, year: i32) -> Result<HM> {
+            Ok(self
+                .people
+                .borrow()
+                .get(person)
+                .and_then(|years| years.get(&year))
+                .cloned()
+                .unwrap_or_default())
+        }
+
+        fn team_days(&self, year: i32) -> Result<HM> {
+            Ok(self
+                .team_days
+                .borrow()
+                .get(&year)
+                .cloned()
+                .unwrap_or_default())
+        }
+    }
+
+    struct TempHome {
+        previous: Option<String>,
+        path: PathBuf,
     }
 
     impl TempHome {
@@ -357,59 +1498,551 @@ mod tests {
         }
     }
 
-    fn test_now(year: i32, month: u32, day: u32) -> DateTime<Utc> {
-        Utc.from_utc_datetime(
-            &NaiveDate::from_ymd_opt(year, month, day)
-                .expect("valid test date")
-                .and_hms_opt(0, 0, 0)
-                .expect("valid test time"),
-        )
+    fn test_now(year: i32, month: u32, day: u32) -> DateTime<Utc> {
+        Utc.from_utc_datetime(
+            &NaiveDate::from_ymd_opt(year, month, day)
+                .expect("valid test date")
+                .and_hms_opt(0, 0, 0)
+                .expect("valid test time"),
+        )
+    }
+
+    #[test]
+    fn display_writes_calendar_to_environment() {
+        let mut holidays = HM::new();
+        holidays.insert((1, 1), HolidayEntry::official("New Year's Day".to_string()));
+        let env = TestEnvironment::new(test_now(1970, 1, 1)).with_holidays(1970, holidays);
+
+        let options = DisplayOptions {
+            mode: Mode::Month,
+            ..DisplayOptions::default()
+        };
+        display(&env, &options).expect("display should succeed");
+
+        let outputs = env.outputs();
+        assert_eq!(outputs.len(), 1);
+        assert!(
+            outputs[0].contains("January 1970"),
+            "expected month header in output"
+        );
+    }
+
+    #[test]
+    fn display_mode_q_renders_calendar_quarter() {
+        let env = TestEnvironment::new(test_now(1970, 11, 1));
+
+        display(&env, &DisplayOptions::default()).expect("display should succeed");
+
+        let output = env
+            .outputs()
+            .into_iter()
+            .next()
+            .expect("expected display output");
+        assert!(output.contains("October 1970"));
+        assert!(output.contains("November 1970"));
+        assert!(output.contains("December 1970"));
+        assert!(!output.contains("September 1970"));
+    }
+
+    #[test]
+    fn display_mode_q_honours_fiscal_year_and_quarter() {
+        let mut previous = HM::new();
+        previous.insert((25, 12), HolidayEntry::official("Christmas".to_string()));
+        let env = TestEnvironment::new(test_now(1971, 2, 1)).with_holidays(1970, previous);
+        let options = DisplayOptions {
+            quarter: Some(Quarter::Q3),
+            fiscal_start: 4,
+            ..DisplayOptions::default()
+        };
+
+        display(&env, &options).expect("display should succeed");
+
+        let output = env
+            .outputs()
+            .into_iter()
+            .next()
+            .expect("expected display output");
+        assert!(output.contains("October 1970"));
+        assert!(output.contains("December 1970"));
+        assert!(!output.contains("January 1971"));
+    }
+
+    #[test]
+    fn display_mode_year_includes_all_months() {
+        let env = TestEnvironment::new(test_now(1970, 6, 1));
+
+        let options = DisplayOptions {
+            mode: Mode::Year,
+            ..DisplayOptions::default()
+        };
+        display(&env, &options).expect("display should succeed");
+
+        let output = env
+            .outputs()
+            .into_iter()
+            .next()
+            .expect("expected display output");
+        assert!(output.contains("January 1970"));
+        assert!(output.contains("December 1970"));
+    }
+
+    #[test]
+    fn display_fits_columns_to_requested_width() {
+        let env = TestEnvironment::new(test_now(1970, 6, 1));
+
+        let options = DisplayOptions {
+            mode: Mode::Year,
+            width: Some(200),
+            ..DisplayOptions::default()
+        };
+        display(&env, &options).expect("display should succeed");
+
+        let output = env
+            .outputs()
+            .into_iter()
+            .next()
+            .expect("expected display output");
+        let first_line = output.lines().next().expect("header row");
+        assert!(first_line.contains("January 1970"));
+        assert!(first_line.contains("June 1970"));
+        assert!(!first_line.contains("July 1970"));
+    }
+
+    #[test]
+    fn display_uses_one_column_on_narrow_terminals() {
+        let env = TestEnvironment::new(test_now(1970, 6, 1));
+        let options = DisplayOptions {
+            width: Some(30),
+            ..DisplayOptions::default()
+        };
+
+        display(&env, &options).expect("display should succeed");
+
+        let output = env
+            .outputs()
+            .into_iter()
+            .next()
+            .expect("expected display output");
+        let first_line = output.lines().next().expect("header row");
+        assert!(first_line.contains("April 1970"));
+        assert!(!first_line.contains("May 1970"));
+    }
+
+    #[test]
+    fn display_renders_html_page() {
+        let mut holidays = HM::new();
+        holidays.insert((1, 1), HolidayEntry::official("New Year's Day".to_string()));
+        let env = TestEnvironment::new(test_now(1970, 1, 1)).with_holidays(1970, holidays);
+        let options = DisplayOptions {
+            mode: Mode::Month,
+            format: DisplayFormat::Html,
+            ..DisplayOptions::default()
+        };
+
+        display(&env, &options).expect("display should succeed");
+
+        let output = env
+            .outputs()
+            .into_iter()
+            .next()
+            .expect("expected display output");
+        assert!(output.starts_with("<!DOCTYPE html>"));
+        assert!(output.contains("<td class=\"holiday today\" title=\"New Year&#39;s Day\">1</td>"));
+    }
+
+    #[test]
+    fn display_emits_json_grid() {
+        let mut holidays = HM::new();
+        holidays.insert((1, 1), HolidayEntry::official("New Year's Day".to_string()));
+        let env = TestEnvironment::new(test_now(1970, 1, 2)).with_holidays(1970, holidays);
+        let options = DisplayOptions {
+            mode: Mode::Month,
+            format: DisplayFormat::Json,
+            ..DisplayOptions::default()
+        };
+
+        display(&env, &options).expect("display should succeed");
+
+        let outputs = env.outputs();
+        let value: serde_json::Value =
+            serde_json::from_str(outputs[0].trim()).expect("valid json output");
+        let first_week = &value[0]["weeks"][0];
+        assert!(first_week[0].is_null());
+        assert_eq!(first_week[3]["date"], "1970-01-01");
+        assert_eq!(first_week[3]["holidays"][0]["name"], "New Year's Day");
+        assert_eq!(first_week[4]["today"], true);
+    }
+
+    #[test]
+    fn display_exports_vector_formats_with_paper_size() {
+        let mut holidays = HM::new();
+        holidays.insert((25, 12), HolidayEntry::official("Christmas".to_string()));
+        let env = TestEnvironment::new(test_now(1970, 6, 1)).with_holidays(1970, holidays);
+
+        let svg = DisplayOptions {
+            mode: Mode::Year,
+            format: DisplayFormat::Svg,
+            paper: Paper::Letter,
+            ..DisplayOptions::default()
+        };
+        display(&env, &svg).expect("svg display should succeed");
+        let pdf = DisplayOptions {
+            format: DisplayFormat::Pdf,
+            ..svg
+        };
+        display(&env, &pdf).expect("pdf display should succeed");
+
+        let outputs = env.outputs();
+        assert!(outputs[0].contains("viewBox=\"0 0 612 792\""));
+        assert!(outputs[0].contains(">Christmas</text>"));
+        assert!(outputs[1].starts_with("%PDF-1.4"));
+        assert!(outputs[1].contains("/MediaBox [0 0 612 792]"));
+        assert!(outputs[1].contains("(Christmas) Tj"));
+    }
+
+    #[test]
+    fn display_localizes_month_titles() {
+        let env = TestEnvironment::new(test_now(1970, 3, 1));
+        let options = DisplayOptions {
+            mode: Mode::Month,
+            locale: Some(Locale::de_DE),
+            ..DisplayOptions::default()
+        };
+
+        display(&env, &options).expect("display should succeed");
+
+        let output = env
+            .outputs()
+            .into_iter()
+            .next()
+            .expect("expected display output");
+        assert!(output.contains("März 1970"));
+        assert!(output.contains("Di"));
+    }
+
+    #[test]
+    fn display_highlights_days_every_country_observes() {
+        let mut holidays = HM::new();
+        holidays.insert(
+            (25, 12),
+            HolidayEntry {
+                countries: vec!["AR".to_string(), "US".to_string()],
+                ..HolidayEntry::official("Navidad / Christmas Day")
+            },
+        );
+        holidays.insert(
+            (8, 12),
+            HolidayEntry {
+                countries: vec!["AR".to_string()],
+                ..HolidayEntry::official("Inmaculada Concepción")
+            },
+        );
+        let env = TestEnvironment::new(test_now(2026, 12, 1))
+            .with_countries(2)
+            .with_holidays(2026, holidays);
+        let options = DisplayOptions {
+            mode: Mode::Month,
+            format: DisplayFormat::Html,
+            ..DisplayOptions::default()
+        };
+
+        display(&env, &options).expect("display should succeed");
+
+        let output = env.outputs().join("");
+        assert!(output.contains(
+            "<td class=\"holiday shared\" title=\"Navidad / Christmas Day (AR, US)\">25</td>"
+        ));
+        assert!(
+            output.contains("<td class=\"holiday\" title=\"Inmaculada Concepción (AR)\">8</td>")
+        );
+    }
+
+    #[test]
+    fn list_annotates_observing_countries() {
+        let mut holidays = HM::new();
+        holidays.insert(
+            (25, 12),
+            HolidayEntry {
+                countries: vec!["AR".to_string(), "US".to_string()],
+                ..HolidayEntry::official("Navidad / Christmas Day")
+            },
+        );
+        let env = TestEnvironment::new(test_now(2026, 6, 1))
+            .with_countries(2)
+            .with_holidays(2026, holidays);
+
+        list(&env, &ListOptions::default()).expect("list should succeed");
+
+        assert_eq!(
+            env.outputs(),
+            vec!["2026-12-25  Navidad / Christmas Day [official; AR, US]\n".to_string()]
+        );
+    }
+
+    #[test]
+    fn compare_reports_counts_unique_and_shared_dates() {
+        let mut ar = HM::new();
+        ar.insert((1, 1), HolidayEntry::official("Año Nuevo"));
+        ar.insert((8, 2), HolidayEntry::official("Carnaval"));
+        let mut de = HM::new();
+        de.insert((1, 1), HolidayEntry::official("Neujahr"));
+        de.insert((3, 10), HolidayEntry::official("Tag der Deutschen Einheit"));
+        de.insert((4, 10), HolidayEntry::official("Brückentag"));
+        let env = TestEnvironment::new(test_now(2026, 6, 1))
+            .with_country_holidays("AR", 2027, ar)
+            .with_country_holidays("DE", 2027, de);
+        let providers =
+            Provider::list_from_args(Some("AR,DE".to_string()), None).expect("valid countries");
+
+        compare(&env, &providers, Some(2027)).expect("compare should succeed");
+
+        let output = env.outputs().join("");
+        let expected = [
+            "Comparison for 2027",
+            "                  AR      DE",
+            "Holidays           2       3",
+            "Working days     259     259",
+            "",
+            "Only in AR:",
+            "  2027-02-08  Carnaval",
+            "",
+            "Only in DE:",
+            "  2027-10-03  Tag der Deutschen Einheit",
+            "  2027-10-04  Brückentag",
+            "",
+            "Shared:",
+            "  2027-01-01  Año Nuevo / Neujahr",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn compare_ignores_custom_entries() {
+        let team_day = HolidayEntry::custom("Team day");
+        let mut ar = HM::new();
+        ar.insert((1, 1), HolidayEntry::official("Año Nuevo"));
+        ar.insert((5, 3), team_day.clone());
+        let mut de = HM::new();
+        de.insert((1, 1), HolidayEntry::official("Neujahr"));
+        de.insert((5, 3), team_day);
+        let env = TestEnvironment::new(test_now(2026, 6, 1))
+            .with_country_holidays("AR", 2027, ar)
+            .with_country_holidays("DE", 2027, de);
+        let providers =
+            Provider::list_from_args(Some("AR,DE".to_string()), None).expect("valid countries");
+
+        compare(&env, &providers, Some(2027)).expect("compare should succeed");
+
+        let output = env.outputs().join("");
+        assert!(output.contains("Holidays           1       1"));
+        assert!(output.contains("Shared:\n  2027-01-01  Año Nuevo / Neujahr\n"));
+        assert!(!output.contains("Team day"));
     }
 
     #[test]
-    fn display_writes_calendar_to_environment() {
+    fn stats_summarizes_holidays_and_working_days() {
         let mut holidays = HM::new();
-        holidays.insert((1, 1), HolidayEntry::official("New Year's Day".to_string()));
-        let env = TestEnvironment::new(test_now(1970, 1, 1)).with_holidays(1970, holidays);
+        holidays.insert((1, 1), HolidayEntry::official("New Year's Day"));
+        holidays.insert((3, 1), HolidayEntry::official("Saturday holiday"));
+        holidays.insert((24, 12), HolidayEntry::custom("Family dinner"));
+        holidays.insert((25, 12), HolidayEntry::official("Christmas Day"));
+        let env = TestEnvironment::new(test_now(2026, 6, 1)).with_holidays(2026, holidays);
+
+        stats(&env, None, None).expect("stats should succeed");
+
+        let output = env.outputs().join("");
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(
+            lines[..5],
+            [
+                "Holidays in 2026: 4",
+                "  custom: 1",
+                "  official: 3",
+                "On weekdays: 3",
+                "On weekends: 1",
+            ]
+        );
+        assert!(lines.contains(&"January            2            21"));
+        assert!(lines.contains(&"February           0            20"));
+        assert!(lines.contains(&"December           2            21"));
+        assert!(lines.contains(&"Total              4           258"));
+        assert!(lines.contains(&"Thu                2"));
+        assert!(lines.contains(&"Fri                1"));
+        assert!(lines.contains(&"Sat                1"));
+    }
+
+    #[test]
+    fn stats_weighs_half_days() {
+        let mut holidays = HM::new();
+        holidays.insert(
+            (24, 12),
+            HolidayEntry {
+                half_day: true,
+                ..HolidayEntry::custom("Christmas Eve")
+            },
+        );
+        holidays.insert((25, 12), HolidayEntry::official("Christmas Day"));
+        let env = TestEnvironment::new(test_now(2026, 6, 1)).with_holidays(2026, holidays);
 
-        display(&env, Mode::Month).expect("display should succeed");
+        stats(&env, None, None).expect("stats should succeed");
 
-        let outputs = env.outputs();
-        assert_eq!(outputs.len(), 1);
-        assert!(
-            outputs[0].contains("January 1970"),
-            "expected month header in output"
+        let output = env.outputs().join("");
+        assert!(output.contains("December         1.5          21.5"));
+        assert!(output.contains("Total            1.5         259.5"));
+    }
+
+    #[test]
+    fn stats_counts_half_day_leave_as_half() {
+        let mut holidays = HM::new();
+        holidays.insert(
+            (23, 12),
+            HolidayEntry {
+                kind: HolidayKind::Leave,
+                half_day: true,
+                ..HolidayEntry::custom("Vacation")
+            },
         );
+        holidays.insert((25, 12), HolidayEntry::official("Christmas Day"));
+        let env = TestEnvironment::new(test_now(2026, 6, 1)).with_holidays(2026, holidays);
+
+        stats(&env, None, None).expect("stats should succeed");
+
+        let output = env.outputs().join("");
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(
+            lines[..5],
+            [
+                "Holidays in 2026: 1.5",
+                "  leave: 0.5",
+                "  official: 1",
+                "On weekdays: 1.5",
+                "On weekends: 0",
+            ]
+        );
+        assert!(lines.contains(&"Wed              0.5"));
+    }
+
+    #[test]
+    fn team_counts_half_day_leave_as_half() {
+        let half_leave = HolidayEntry {
+            kind: HolidayKind::Leave,
+            half_day: true,
+            ..HolidayEntry::custom("Vacation")
+        };
+        let mut alice = HM::new();
+        alice.insert((2, 2), half_leave.clone());
+        alice.insert((3, 2), half_leave.clone());
+        let mut bob = HM::new();
+        bob.insert((2, 2), HolidayEntry::custom("Dentist"));
+        bob.insert((3, 2), half_leave);
+        let env = TestEnvironment::new(test_now(2027, 2, 1))
+            .with_person("alice", 2027, alice)
+            .with_person("bob", 2027, bob);
+
+        team(&env, None, None, None).expect("team should succeed");
+
+        let output = env.outputs().join("");
+        let out = output
+            .lines()
+            .find(|line| line.starts_with("Out"))
+            .expect("out row");
+        let cells: String = out.chars().take(5 + 3 * 4).collect();
+        assert_eq!(cells, "Out    0 1½  1  0");
     }
 
     #[test]
-    fn display_mode_q_includes_prev_and_next_months() {
-        let env = TestEnvironment::new(test_now(1970, 1, 1));
+    fn stats_localizes_month_and_weekday_names() {
+        let mut holidays = HM::new();
+        holidays.insert((1, 1), HolidayEntry::official("Año Nuevo"));
+        let env = TestEnvironment::new(test_now(2026, 6, 1)).with_holidays(2026, holidays);
 
-        display(&env, Mode::Q).expect("display should succeed");
+        stats(&env, None, Some(Locale::es_AR)).expect("stats should succeed");
 
-        let output = env
-            .outputs()
-            .into_iter()
-            .next()
-            .expect("expected display output");
-        assert!(output.contains("December 1969"));
-        assert!(output.contains("February 1970"));
+        let output = env.outputs().join("");
+        let lines: Vec<&str> = output.lines().collect();
+        assert!(lines.contains(&"Enero              1            21"));
+        assert!(lines.contains(&"Septiembre         0            22"));
+        assert!(lines.contains(&"Jue                1"));
+    }
+
+    fn carnaval_env() -> TestEnvironment {
+        let mut env = TestEnvironment::new(test_now(2026, 6, 1));
+        for (year, day, month) in [(2026, 16, 2), (2027, 8, 2), (2028, 28, 2)] {
+            let mut hm = HM::new();
+            hm.insert((day, month), HolidayEntry::official("Carnaval"));
+            hm.insert((1, 5), HolidayEntry::official("Día del Trabajador"));
+            env = env.with_holidays(year, hm);
+        }
+        env
     }
 
     #[test]
-    fn display_mode_year_includes_all_months() {
-        let env = TestEnvironment::new(test_now(1970, 6, 1));
+    fn search_finds_names_across_years() {
+        let env = carnaval_env();
+        let options = SearchOptions {
+            pattern: "carnaval".to_string(),
+            years: Some(2026..=2028),
+            regex: false,
+        };
+
+        search(&env, &options).expect("search should succeed");
+
+        assert_eq!(
+            env.outputs(),
+            vec![
+                "2026-02-16  Carnaval [official]\n2027-02-08  Carnaval [official]\n2028-02-28  Carnaval [official]\n"
+                    .to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn search_supports_regular_expressions() {
+        let env = carnaval_env();
+        let options = SearchOptions {
+            pattern: "^d[ií]a".to_string(),
+            years: None,
+            regex: true,
+        };
+
+        search(&env, &options).expect("search should succeed");
+        assert_eq!(
+            env.outputs(),
+            vec!["2026-05-01  Día del Trabajador [official]\n".to_string()]
+        );
 
-        display(&env, Mode::Year).expect("display should succeed");
+        let invalid = SearchOptions {
+            pattern: "(".to_string(),
+            years: None,
+            regex: true,
+        };
+        assert!(matches!(search(&env, &invalid), Err(CalError::Config(_))));
+    }
 
-        let output = env
-            .outputs()
-            .into_iter()
-            .next()
-            .expect("expected display output");
-        assert!(output.contains("January 1970"));
-        assert!(output.contains("December 1970"));
+    #[test]
+    fn search_reports_missing_matches() {
+        let env = carnaval_env();
+        let options = SearchOptions {
+            pattern: "Easter".to_string(),
+            years: Some(2026..=2027),
+            regex: false,
+        };
+
+        search(&env, &options).expect("search should succeed");
+        assert_eq!(
+            env.outputs(),
+            vec!["No holidays matching \"Easter\"\n".to_string()]
+        );
+    }
+
+    #[test]
+    fn columns_for_width_falls_back_without_terminal() {
+        assert_eq!(columns_for_width(None), DEFAULT_COLUMNS);
+        assert_eq!(columns_for_width(Some(0)), 1);
+        assert_eq!(columns_for_width(Some(display_month::WIDTH * 4)), 4);
+        assert_eq!(columns_for_width(Some(1000)), MAX_COLUMNS);
     }
 
     #[test]
@@ -419,7 +2052,7 @@ mod tests {
         holidays.insert((24, 12), HolidayEntry::custom("Family dinner".to_string()));
         let env = TestEnvironment::new(test_now(2024, 6, 1)).with_holidays(2024, holidays);
 
-        list(&env, OutputFormat::Table).expect("list should succeed");
+        list(&env, &ListOptions::default()).expect("list should succeed");
 
         let outputs = env.outputs();
         assert_eq!(outputs.len(), 1);
@@ -438,7 +2071,7 @@ mod tests {
         );
         let env = TestEnvironment::new(test_now(2024, 5, 1)).with_holidays(2024, holidays);
 
-        list(&env, OutputFormat::Table).expect("list should succeed");
+        list(&env, &ListOptions::default()).expect("list should succeed");
 
         let output = env
             .outputs()
@@ -450,22 +2083,100 @@ mod tests {
         assert_eq!(lines.next(), Some("2024-05-10  Later Holiday [official]"));
     }
 
+    #[test]
+    fn list_annotates_regional_holidays() {
+        let mut holidays = HM::new();
+        holidays.insert(
+            (4, 6),
+            HolidayEntry {
+                regions: vec!["DE-BW".to_string(), "DE-BY".to_string()],
+                ..HolidayEntry::official("Corpus Christi")
+            },
+        );
+        let env = TestEnvironment::new(test_now(2026, 6, 1)).with_holidays(2026, holidays);
+
+        list(&env, &ListOptions::default()).expect("list should succeed");
+
+        assert_eq!(
+            env.outputs(),
+            vec!["2026-06-04  Corpus Christi [official] (DE-BW, DE-BY)\n".to_string()]
+        );
+    }
+
+    #[test]
+    fn list_localizes_dates_in_table_output() {
+        let mut holidays = HM::new();
+        holidays.insert((25, 12), HolidayEntry::official("Navidad".to_string()));
+        let env = TestEnvironment::new(test_now(2024, 6, 1)).with_holidays(2024, holidays);
+        let options = ListOptions {
+            locale: Some(Locale::de_DE),
+            ..ListOptions::default()
+        };
+
+        list(&env, &options).expect("list should succeed");
+
+        assert_eq!(
+            env.outputs(),
+            vec!["25.12.2024  Navidad [official]\n".to_string()]
+        );
+    }
+
     #[test]
     fn list_informs_when_no_holidays_available() {
         let env = TestEnvironment::new(test_now(2024, 6, 1));
 
-        list(&env, OutputFormat::Table).expect("list should succeed");
+        list(&env, &ListOptions::default()).expect("list should succeed");
 
         assert_eq!(env.outputs(), vec!["No holidays found\n".to_string()]);
     }
 
+    #[test]
+    fn list_prints_empty_documents_for_machine_formats() {
+        let output = |format| {
+            let env = TestEnvironment::new(test_now(2024, 6, 1));
+            let options = ListOptions {
+                format,
+                ..ListOptions::default()
+            };
+            list(&env, &options).expect("list should succeed");
+            env.outputs().concat()
+        };
+
+        assert_eq!(output(OutputFormat::Csv), "date,name,kind\n");
+        assert_eq!(output(OutputFormat::Tsv), "date\tname\tkind\n");
+        assert_eq!(output(OutputFormat::Json), "[]\n");
+        assert_eq!(output(OutputFormat::Markdown).lines().count(), 2);
+    }
+
+    #[test]
+    fn list_rejects_provider_with_several_countries() {
+        let env = TestEnvironment::new(test_now(2024, 6, 1)).with_countries(2);
+        let columns = ListOptions {
+            format: OutputFormat::Csv,
+            columns: vec![ListColumn::Date, ListColumn::Provider],
+            ..ListOptions::default()
+        };
+        assert!(matches!(list(&env, &columns), Err(CalError::Config(_))));
+
+        let template = ListOptions {
+            template: Some(Template::parse("{date} {provider}").expect("valid template")),
+            ..ListOptions::default()
+        };
+        assert!(matches!(list(&env, &template), Err(CalError::Config(_))));
+        assert!(env.outputs().is_empty());
+    }
+
     #[test]
     fn list_outputs_json() {
         let mut holidays = HM::new();
         holidays.insert((1, 1), HolidayEntry::official("New Year's Day".to_string()));
         let env = TestEnvironment::new(test_now(2024, 6, 1)).with_holidays(2024, holidays);
 
-        list(&env, OutputFormat::Json).expect("list should succeed");
+        let options = ListOptions {
+            format: OutputFormat::Json,
+            ..ListOptions::default()
+        };
+        list(&env, &options).expect("list should succeed");
 
         let outputs = env.outputs();
         assert_eq!(outputs.len(), 1);
@@ -481,7 +2192,11 @@ mod tests {
         holidays.insert((1, 1), HolidayEntry::official("New Year's Day".to_string()));
         let env = TestEnvironment::new(test_now(2024, 6, 1)).with_holidays(2024, holidays);
 
-        list(&env, OutputFormat::Markdown).expect("list should succeed");
+        let options = ListOptions {
+            format: OutputFormat::Markdown,
+            ..ListOptions::default()
+        };
+        list(&env, &options).expect("list should succeed");
 
         let outputs = env.outputs();
         assert_eq!(outputs.len(), 1);
@@ -498,11 +2213,77 @@ mod tests {
         assert!(cells.contains(&"official"));
     }
 
+    #[test]
+    fn list_outputs_csv_with_quoted_names() {
+        let mut holidays = HM::new();
+        holidays.insert((1, 1), HolidayEntry::official("New Year's Day".to_string()));
+        holidays.insert(
+            (24, 12),
+            HolidayEntry::custom("Dinner, \"family\" edition".to_string()),
+        );
+        let env = TestEnvironment::new(test_now(2024, 6, 1)).with_holidays(2024, holidays);
+        let options = ListOptions {
+            format: OutputFormat::Csv,
+            ..ListOptions::default()
+        };
+
+        list(&env, &options).expect("list should succeed");
+
+        let outputs = env.outputs();
+        let mut lines = outputs[0].lines();
+        assert_eq!(lines.next(), Some("date,name,kind"));
+        assert_eq!(lines.next(), Some("2024-01-01,New Year's Day,official"));
+        assert_eq!(
+            lines.next(),
+            Some("2024-12-24,\"Dinner, \"\"family\"\" edition\",custom")
+        );
+    }
+
+    #[test]
+    fn list_outputs_tsv_with_selected_columns() {
+        let mut holidays = HM::new();
+        holidays.insert((1, 1), HolidayEntry::official("New\tYear".to_string()));
+        let env = TestEnvironment::new(test_now(2024, 6, 1)).with_holidays(2024, holidays);
+        let options = ListOptions {
+            format: OutputFormat::Tsv,
+            columns: vec![ListColumn::Weekday, ListColumn::Name, ListColumn::Provider],
+            ..ListOptions::default()
+        };
+
+        list(&env, &options).expect("list should succeed");
+
+        let outputs = env.outputs();
+        let mut lines = outputs[0].lines();
+        assert_eq!(lines.next(), Some("weekday\tname\tprovider"));
+        assert_eq!(lines.next(), Some("Mon\tNew Year\targentina-datos"));
+    }
+
+    #[test]
+    fn list_renders_each_holiday_through_template() {
+        let mut holidays = HM::new();
+        holidays.insert((1, 1), HolidayEntry::official("New Year's Day".to_string()));
+        holidays.insert((24, 12), HolidayEntry::custom("Family dinner".to_string()));
+        let env = TestEnvironment::new(test_now(2024, 6, 1)).with_holidays(2024, holidays);
+        let options = ListOptions {
+            template: Some(
+                Template::parse("{date:%a %d %b} {name} ({kind})").expect("valid template"),
+            ),
+            ..ListOptions::default()
+        };
+
+        list(&env, &options).expect("list should succeed");
+
+        assert_eq!(
+            env.outputs(),
+            vec!["Mon 01 Jan New Year's Day (official)\nTue 24 Dec Family dinner (custom)\n"]
+        );
+    }
+
     #[test]
     fn add_stores_holiday_and_prints_ok() {
         let env = TestEnvironment::new(test_now(2024, 5, 1));
 
-        add(&env, 24, 12, None).expect("add should succeed");
+        add(&env, day(2024, 12, 24), &AddOptions::default()).expect("add should succeed");
 
         let stored = env.stored(2024).expect("holiday map stored");
         let entry = stored
@@ -519,9 +2300,11 @@ mod tests {
 
         add(
             &env,
-            2,
-            7,
-            Some("  Family gathering  ".to_string()),
+            day(2024, 7, 2),
+            &AddOptions {
+                description: Some("  Family gathering  ".to_string()),
+                ..AddOptions::default()
+            },
         )
         .expect("add should succeed");
 
@@ -533,18 +2316,284 @@ mod tests {
         assert_eq!(entry.name, "Family gathering");
     }
 
+    #[test]
+    fn add_records_half_days() {
+        let env = TestEnvironment::new(test_now(2026, 10, 18));
+
+        add(
+            &env,
+            day(2026, 12, 31),
+            &AddOptions {
+                half_day: true,
+                ..AddOptions::default()
+            },
+        )
+        .expect("add half day");
+
+        let stored = env.stored(2026).expect("holiday map stored");
+        assert!(stored[&(31, 12)].half_day);
+    }
+
+    #[test]
+    fn list_and_display_filter_by_tag() {
+        let mut holidays = HM::new();
+        holidays.insert((1, 1), HolidayEntry::official("New Year's Day"));
+        holidays.insert(
+            (5, 1),
+            HolidayEntry {
+                tags: vec!["oncall".to_string()],
+                ..HolidayEntry::custom("Rotation")
+            },
+        );
+        holidays.insert(
+            (9, 1),
+            HolidayEntry {
+                tags: vec!["conference".to_string(), "travel".to_string()],
+                ..HolidayEntry::custom("FOSDEM")
+            },
+        );
+        let env = TestEnvironment::new(test_now(2026, 1, 2)).with_holidays(2026, holidays);
+
+        let options = ListOptions {
+            tags: vec!["travel".to_string(), "oncall".to_string()],
+            ..ListOptions::default()
+        };
+        list(&env, &options).expect("list should succeed");
+        assert_eq!(
+            env.outputs(),
+            vec![
+                "2026-01-05  Rotation [custom] #oncall\n2026-01-09  FOSDEM [custom] #conference #travel\n"
+            ]
+        );
+
+        env.output.borrow_mut().clear();
+        let options = DisplayOptions {
+            mode: Mode::Month,
+            format: DisplayFormat::Json,
+            tags: vec!["conference".to_string()],
+            ..DisplayOptions::default()
+        };
+        display(&env, &options).expect("display should succeed");
+        let output = env.outputs().join("");
+        assert!(output.contains("FOSDEM"));
+        assert!(output.contains("\"tags\": ["));
+        assert!(!output.contains("New Year's Day"));
+        assert!(!output.contains("Rotation"));
+    }
+
+    #[test]
+    fn add_records_tags() {
+        let env = TestEnvironment::new(test_now(2026, 10, 18));
+
+        add(
+            &env,
+            day(2026, 11, 3),
+            &AddOptions {
+                tags: vec!["oncall".to_string()],
+                ..AddOptions::default()
+            },
+        )
+        .expect("add tagged day");
+
+        let stored = env.stored(2026).expect("holiday map stored");
+        assert_eq!(stored[&(3, 11)].tags, ["oncall"]);
+    }
+
+    #[test]
+    fn team_shows_who_is_out_each_day() {
+        let mut shared = HM::new();
+        shared.insert((15, 2), HolidayEntry::official("Carnaval"));
+        let mut alice = HM::new();
+        alice.insert((2, 2), HolidayEntry::custom("Dentist"));
+        alice.insert(
+            (3, 2),
+            HolidayEntry {
+                half_day: true,
+                ..HolidayEntry::custom("School play")
+            },
+        );
+        let mut bob = HM::new();
+        bob.insert(
+            (2, 2),
+            HolidayEntry {
+                kind: HolidayKind::Leave,
+                ..HolidayEntry::custom("Vacation")
+            },
+        );
+        bob.insert((6, 2), HolidayEntry::custom("Saturday trip"));
+        let env = TestEnvironment::new(test_now(2027, 2, 1))
+            .with_holidays(2027, shared)
+            .with_person("alice", 2027, alice)
+            .with_person("bob", 2027, bob);
+
+        team(&env, None, None, None).expect("team should succeed");
+
+        let output = env.outputs().join("");
+        let lines: Vec<&str> = output.lines().collect();
+        let prefix = |line: &str, days: usize| line.chars().take(5 + 3 * days).collect::<String>();
+        assert_eq!(lines[0], "Team February 2027");
+        assert_eq!(prefix(lines[1], 7), "      Mo Tu We Th Fr Sa Su");
+        assert_eq!(prefix(lines[2], 7), "       1  2  3  4  5  6  7");
+        assert_eq!(prefix(lines[3], 7), "alice  .  x  /  .  .  -  -");
+        assert_eq!(prefix(lines[4], 7), "bob    .  x  .  .  .  -  -");
+        assert_eq!(prefix(lines[5], 7), "Out    0  2  ½  0  0      ");
+        assert_eq!(&lines[3][5 + 3 * 14..5 + 3 * 15], "  H");
+        assert_eq!(lines[7], "x day off, / half day, H holiday, - weekend");
+    }
+
+    #[test]
+    fn team_closes_only_official_and_shared_days() {
+        let mut holidays = HM::new();
+        holidays.insert((1, 2), HolidayEntry::official("Feriado"));
+        holidays.insert((2, 2), HolidayEntry::custom("Lead's day off"));
+        let mut shared = HM::new();
+        shared.insert((3, 2), HolidayEntry::custom("Offsite"));
+        let mut alice = HM::new();
+        alice.insert((4, 2), HolidayEntry::custom("Dentist"));
+        let env = TestEnvironment::new(test_now(2027, 2, 1))
+            .with_holidays(2027, holidays)
+            .with_team_days(2027, shared)
+            .with_person("alice", 2027, alice);
+
+        team(&env, None, None, Some(Locale::es_ES)).expect("team should succeed");
+
+        let output = env.outputs().join("");
+        let lines: Vec<&str> = output.lines().collect();
+        let prefix = |line: &str, days: usize| line.chars().take(5 + 3 * days).collect::<String>();
+        assert_eq!(lines[0], "Team Febrero 2027");
+        assert_eq!(prefix(lines[1], 4), "      Lu Ma Mi Ju");
+        assert_eq!(prefix(lines[3], 4), "alice  H  .  H  x");
+    }
+
+    #[test]
+    fn team_without_people_explains_how_to_add_them() {
+        let env = TestEnvironment::new(test_now(2027, 2, 1));
+        team(&env, Some(3), None, None).expect("team should succeed");
+        assert_eq!(
+            env.outputs(),
+            vec!["No people found; add entries with cal2 --person <NAME> add\n"]
+        );
+    }
+
+    #[test]
+    fn add_and_delete_use_the_entry_year() {
+        let env = TestEnvironment::new(test_now(2026, 10, 18));
+
+        add(
+            &env,
+            day(2027, 1, 4),
+            &AddOptions {
+                description: Some("Vacation".to_string()),
+                ..AddOptions::default()
+            },
+        )
+        .expect("add next year");
+        assert!(env.stored(2026).is_none());
+        let stored = env.stored(2027).expect("next year's map stored");
+        assert_eq!(stored[&(4, 1)].name, "Vacation");
+
+        delete(&env, day(2027, 1, 4)).expect("delete next year");
+        assert!(env.stored(2027).expect("map saved").is_empty());
+        assert!(env.stored(2026).is_none());
+    }
+
     #[test]
     fn add_does_not_override_existing_official_holiday() {
+        let mut holidays = HM::new();
+        holidays.insert((1, 5), HolidayEntry::official("Labour Day".to_string()));
+        let env = TestEnvironment::new(test_now(2024, 5, 1)).with_holidays(2024, holidays);
+
+        let err =
+            add(&env, day(2024, 5, 1), &AddOptions::default()).expect_err("official holidays stay");
+
+        assert!(matches!(err, CalError::Entry(_)));
+        assert!(env.stored(2024).is_none());
+        assert!(env.outputs().is_empty());
+    }
+
+    #[test]
+    fn add_refuses_days_that_already_have_an_entry() {
         let mut store = HM::new();
-        store.insert((1, 5), HolidayEntry::official("Labour Day".to_string()));
-        let env = TestEnvironment::new(test_now(2024, 5, 1)).with_store(2024, store);
+        store.insert((24, 12), HolidayEntry::custom("Family dinner"));
+        let env = TestEnvironment::new(test_now(2026, 5, 1)).with_store(2026, store.clone());
+        let options = AddOptions {
+            description: Some("Christmas Eve".to_string()),
+            ..AddOptions::default()
+        };
+
+        let err = add(&env, day(2026, 12, 24), &options).expect_err("existing entry stays");
+
+        assert!(err.to_string().contains("Family dinner"));
+        assert!(err.to_string().contains("cal2 edit"));
+        assert_eq!(env.stored(2026), Some(store));
+        assert!(env.outputs().is_empty());
+    }
 
-        add(&env, 1, 5, None).expect("add should succeed");
+    #[test]
+    fn add_rule_records_recurring_entry_once() {
+        let env = TestEnvironment::new(test_now(2026, 10, 18));
 
-        let stored = env.stored(2024).expect("holiday map stored");
-        let entry = stored.get(&(1, 5)).expect("holiday should remain present");
-        assert_eq!(entry.kind, HolidayKind::Official);
-        assert_eq!(entry.name, "Labour Day");
+        add_rule(
+            &env,
+            day(2026, 11, 6),
+            Some("Team day off".to_string()),
+            Recurrence::Monthly,
+        )
+        .expect("add rule");
+        assert!(
+            add_rule(
+                &env,
+                day(2026, 11, 6),
+                Some("Team day off".to_string()),
+                Recurrence::Monthly,
+            )
+            .is_err()
+        );
+
+        assert_eq!(
+            env.rules().expect("rules"),
+            vec![Rule::new(
+                day(2026, 11, 6),
+                Recurrence::Monthly,
+                "Team day off".to_string()
+            )]
+        );
+        assert!(env.stored(2026).is_none());
+        assert_eq!(
+            env.outputs(),
+            vec!["OK: Team day off every first Friday of the month\n"]
+        );
+    }
+
+    #[test]
+    fn rules_lists_and_removes_recurring_entries() {
+        let env = TestEnvironment::new(test_now(2026, 10, 18));
+        rules(&env, None).expect("empty listing");
+        assert_eq!(env.outputs(), vec!["No recurring entries.\n"]);
+        add_rule(&env, day(2026, 3, 14), None, Recurrence::Yearly).expect("add yearly");
+        add_rule(
+            &env,
+            day(2026, 10, 9),
+            Some("9/80".to_string()),
+            Recurrence::Biweekly,
+        )
+        .expect("add biweekly");
+        env.output.borrow_mut().clear();
+
+        rules(&env, None).expect("listing");
+        assert_eq!(
+            env.outputs(),
+            vec![
+                " 1. Recurring day off - every year on 14 March since 2026-03-14 (next 2027-03-14)\n",
+                " 2. 9/80 - every other Friday since 2026-10-09 (next 2026-10-23)\n",
+            ]
+        );
+
+        assert!(rules(&env, Some(3)).is_err());
+        rules(&env, Some(1)).expect("remove");
+        let remaining = env.rules().expect("rules");
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].name, "9/80");
     }
 
     #[test]
@@ -554,13 +2603,181 @@ mod tests {
         store.insert((24, 12), HolidayEntry::custom("Family dinner".to_string()));
         let env = TestEnvironment::new(test_now(2024, 5, 1)).with_store(2024, store);
 
-        delete(&env, 24, 12).expect("delete should succeed");
+        delete(&env, day(2024, 12, 24)).expect("delete should succeed");
 
         let stored = env.stored(2024).expect("holiday map stored");
         assert!(!stored.contains_key(&(24, 12)));
         assert_eq!(env.outputs(), vec!["OK\n".to_string()]);
     }
 
+    fn edit_env() -> TestEnvironment {
+        let mut holidays = HM::new();
+        holidays.insert((1, 5), HolidayEntry::official("Labour Day"));
+        let mut store = HM::new();
+        store.insert((24, 12), HolidayEntry::custom("Family dinner"));
+        store.insert((28, 12), HolidayEntry::custom("Ski trip"));
+        TestEnvironment::new(test_now(2026, 5, 1))
+            .with_holidays(2026, holidays)
+            .with_store(2026, store)
+    }
+
+    fn day(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).expect("valid date")
+    }
+
+    #[test]
+    fn edit_renames_and_retypes_custom_entries() {
+        let env = edit_env();
+        let options = EditOptions {
+            description: Some(" Christmas Eve dinner ".to_string()),
+            kind: Some(HolidayKind::Leave),
+            ..EditOptions::default()
+        };
+
+        edit(&env, day(2026, 12, 24), &options).expect("edit should succeed");
+
+        let stored = env.stored(2026).expect("holiday map stored");
+        let entry = stored.get(&(24, 12)).expect("entry still present");
+        assert_eq!(entry.name, "Christmas Eve dinner");
+        assert_eq!(entry.kind, HolidayKind::Leave);
+        assert_eq!(env.outputs(), vec!["OK\n".to_string()]);
+    }
+
+    #[test]
+    fn edit_moves_entries_within_and_across_years() {
+        let env = edit_env();
+        let within = EditOptions {
+            move_to: Some(day(2026, 12, 23)),
+            ..EditOptions::default()
+        };
+        edit(&env, day(2026, 12, 24), &within).expect("move within the year");
+        let stored = env.stored(2026).expect("holiday map stored");
+        assert!(!stored.contains_key(&(24, 12)));
+        assert_eq!(
+            stored.get(&(23, 12)).map(|e| e.name.as_str()),
+            Some("Family dinner")
+        );
+
+        let across = EditOptions {
+            move_to: Some(day(2027, 1, 4)),
+            ..EditOptions::default()
+        };
+        edit(&env, day(2026, 12, 28), &across).expect("move into next year");
+        assert!(
+            !env.stored(2026)
+                .expect("source year")
+                .contains_key(&(28, 12))
+        );
+        assert_eq!(
+            env.stored(2027)
+                .expect("target year")
+                .get(&(4, 1))
+                .map(|e| e.name.as_str()),
+            Some("Ski trip")
+        );
+    }
+
+    #[test]
+    fn edit_rejects_official_missing_and_occupied_dates() {
+        let env = edit_env();
+        let rename = EditOptions {
+            description: Some("Worked".to_string()),
+            ..EditOptions::default()
+        };
+        for date in [day(2026, 5, 1), day(2026, 7, 9)] {
+            assert!(matches!(edit(&env, date, &rename), Err(CalError::Entry(_))));
+        }
+
+        let onto_official = EditOptions {
+            move_to: Some(day(2026, 5, 1)),
+            ..EditOptions::default()
+        };
+        assert!(matches!(
+            edit(&env, day(2026, 12, 24), &onto_official),
+            Err(CalError::Entry(_))
+        ));
+        let stored = env.stored(2026).expect("holiday map stored");
+        assert_eq!(
+            stored.get(&(24, 12)).map(|e| e.name.as_str()),
+            Some("Family dinner")
+        );
+        assert!(env.outputs().is_empty());
+    }
+
+    #[test]
+    fn hide_and_unhide_record_reversible_overrides() {
+        let mut holidays = HM::new();
+        holidays.insert((1, 5), HolidayEntry::official("Labour Day"));
+        holidays.insert((24, 12), HolidayEntry::custom("Family dinner"));
+        let env = TestEnvironment::new(test_now(2026, 5, 1)).with_holidays(2026, holidays);
+
+        hide(&env, day(2026, 5, 1)).expect("hide official holiday");
+        assert_eq!(env.hidden(2026).expect("overrides"), Hidden::from([(1, 5)]));
+        assert!(matches!(
+            hide(&env, day(2026, 12, 24)),
+            Err(CalError::Entry(_))
+        ));
+
+        unhide(&env, day(2026, 5, 1)).expect("unhide official holiday");
+        assert!(env.hidden(2026).expect("overrides").is_empty());
+        assert!(matches!(
+            unhide(&env, day(2026, 5, 1)),
+            Err(CalError::Entry(_))
+        ));
+    }
+
+    #[test]
+    fn list_shows_worked_holidays_only_with_all() {
+        let mut holidays = HM::new();
+        holidays.insert(
+            (1, 5),
+            HolidayEntry {
+                kind: HolidayKind::Worked,
+                ..HolidayEntry::official("Labour Day")
+            },
+        );
+        holidays.insert((25, 12), HolidayEntry::official("Christmas Day"));
+        let env = TestEnvironment::new(test_now(2026, 6, 1)).with_holidays(2026, holidays);
+
+        list(&env, &ListOptions::default()).expect("list should succeed");
+        let options = ListOptions {
+            all: true,
+            ..ListOptions::default()
+        };
+        list(&env, &options).expect("list --all should succeed");
+
+        assert_eq!(
+            env.outputs(),
+            vec![
+                "2026-12-25  Christmas Day [official]\n".to_string(),
+                "2026-05-01  Labour Day [worked]\n2026-12-25  Christmas Day [official]\n"
+                    .to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn delete_reports_missing_entries() {
+        let env = TestEnvironment::new(test_now(2026, 5, 1));
+
+        let err = delete(&env, day(2026, 7, 9)).expect_err("nothing to delete");
+
+        assert!(matches!(err, CalError::Entry(_)));
+        assert!(env.stored(2026).is_none());
+        assert!(env.outputs().is_empty());
+    }
+
+    #[test]
+    fn delete_refuses_official_holidays() {
+        let mut holidays = HM::new();
+        holidays.insert((1, 1), HolidayEntry::official("New Year's Day"));
+        let env = TestEnvironment::new(test_now(2026, 5, 1)).with_holidays(2026, holidays);
+
+        let err = delete(&env, day(2026, 1, 1)).expect_err("official holidays stay");
+        assert!(matches!(err, CalError::Entry(_)));
+        assert!(env.stored(2026).is_none());
+    }
+
     #[test]
     #[serial]
     fn real_environment_roundtrip_uses_cache() {
@@ -573,17 +2790,114 @@ mod tests {
         }
         let mut hm = HM::new();
         hm.insert((4, 3), HolidayEntry::official("Cache Test".to_string()));
+        save(&fname, &hm).expect("save cache");
+        let mut custom = HM::new();
+        custom.insert((5, 3), HolidayEntry::custom("Custom Test"));
 
         let env = RealEnvironment::new(provider);
-        env.save(year, &hm).expect("save cache");
+        env.save(year, &custom).expect("save custom entries");
 
-        let loaded = env.load(year).expect("load cache");
-        assert_eq!(loaded, hm);
+        let loaded = env.load(year).expect("load custom entries");
+        assert_eq!(loaded, custom);
 
         let holidays = env.holidays(year).expect("holidays should load");
-        assert_eq!(holidays, hm);
+        assert_eq!(holidays.len(), 2);
+        assert_eq!(holidays[&(4, 3)], hm[&(4, 3)]);
+        assert_eq!(holidays[&(5, 3)], custom[&(5, 3)]);
 
         env.print("noop").expect("print works");
         env.println("noop").expect("println works");
     }
+
+    fn config_dir() -> PathBuf {
+        let dir = PathBuf::from(shellexpand::tilde("~/.config").to_string());
+        fs::create_dir_all(&dir).expect("create config directory");
+        dir
+    }
+
+    fn official_cache(year: i32, provider: &Provider) {
+        // Stands in for the first fetch of the year's official holidays.
+        let mut hm = HM::new();
+        hm.insert((1, 1), HolidayEntry::official("Año Nuevo"));
+        save(&get_filename(year, provider), &hm).expect("save official cache");
+    }
+
+    #[test]
+    #[serial]
+    fn saving_into_an_uncached_year_leaves_the_provider_cache_alone() {
+        let _home = TempHome::new("save-uncached");
+        config_dir();
+        let provider = Provider::default();
+        let env = RealEnvironment::new(provider.clone());
+        let mut custom = HM::new();
+        custom.insert((10, 3), HolidayEntry::custom("Vacation"));
+
+        env.save(2043, &custom).expect("save custom entries");
+
+        assert!(!Path::new(&get_filename(2043, &provider)).exists());
+        official_cache(2043, &provider);
+        let holidays = env.holidays(2043).expect("holidays should load");
+        assert_eq!(holidays[&(1, 1)].kind, HolidayKind::Official);
+        assert_eq!(holidays[&(10, 3)].name, "Vacation");
+    }
+
+    #[test]
+    #[serial]
+    fn edit_moves_entries_into_another_year() {
+        let _home = TempHome::new("edit-years");
+        config_dir();
+        let provider = Provider::default();
+        official_cache(2042, &provider);
+        official_cache(2043, &provider);
+        let env = RealEnvironment::new(provider.clone());
+        let mut custom = HM::new();
+        custom.insert((28, 12), HolidayEntry::custom("Vacation"));
+        env.save(2042, &custom).expect("save custom entries");
+
+        let options = EditOptions {
+            move_to: Some(day(2043, 1, 4)),
+            ..EditOptions::default()
+        };
+        edit(&env, day(2042, 12, 28), &options).expect("edit should succeed");
+
+        assert!(env.load(2042).expect("old year").is_empty());
+        let cached = load(&get_filename(2043, &provider))
+            .expect("load cache")
+            .expect("cache kept");
+        assert_eq!(cached.len(), 1);
+        let holidays = env.holidays(2043).expect("holidays should load");
+        assert_eq!(holidays[&(1, 1)].kind, HolidayKind::Official);
+        assert_eq!(holidays[&(4, 1)].name, "Vacation");
+    }
+
+    #[test]
+    #[serial]
+    fn real_environment_guards_official_days() {
+        let _home = TempHome::new("official-guard");
+        config_dir();
+        let provider = Provider::default();
+        official_cache(2042, &provider);
+        let env = RealEnvironment::new(provider);
+        let mut custom = HM::new();
+        custom.insert((28, 12), HolidayEntry::custom("Vacation"));
+        env.save(2042, &custom).expect("save custom entries");
+
+        let onto_official = EditOptions {
+            move_to: Some(day(2042, 1, 1)),
+            ..EditOptions::default()
+        };
+        assert!(matches!(
+            edit(&env, day(2042, 12, 28), &onto_official),
+            Err(CalError::Entry(_))
+        ));
+        assert!(matches!(
+            delete(&env, day(2042, 1, 1)),
+            Err(CalError::Entry(_))
+        ));
+        assert!(matches!(
+            add(&env, day(2042, 1, 1), &AddOptions::default()),
+            Err(CalError::Entry(_))
+        ));
+        assert_eq!(env.load(2042).expect("custom entries"), custom);
+    }
 }
@@ -0,0 +1,200 @@
+use crate::error::{CalError, Result};
+use serde::Deserialize;
+use std::{collections::BTreeMap, fs, io};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TagColor {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+}
+
+pub type TagColors = BTreeMap<String, TagColor>;
+
+impl TagColor {
+    const ALL: [TagColor; 8] = [
+        TagColor::Black,
+        TagColor::Red,
+        TagColor::Green,
+        TagColor::Yellow,
+        TagColor::Blue,
+        TagColor::Magenta,
+        TagColor::Cyan,
+        TagColor::White,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            TagColor::Black => "black",
+            TagColor::Red => "red",
+            TagColor::Green => "green",
+            TagColor::Yellow => "yellow",
+            TagColor::Blue => "blue",
+            TagColor::Magenta => "magenta",
+            TagColor::Cyan => "cyan",
+            TagColor::White => "white",
+        }
+    }
+
+    pub fn terminal(self) -> colored::Color {
+        match self {
+            TagColor::Black => colored::Color::Black,
+            TagColor::Red => colored::Color::Red,
+            TagColor::Green => colored::Color::Green,
+            TagColor::Yellow => colored::Color::Yellow,
+            TagColor::Blue => colored::Color::Blue,
+            TagColor::Magenta => colored::Color::Magenta,
+            TagColor::Cyan => colored::Color::Cyan,
+            TagColor::White => colored::Color::White,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq)]
+#[serde(default)]
+pub struct Config {
+    pub fiscal_year_start: Option<u32>,
+    pub locale: Option<String>,
+    pub language: Option<String>,
+    pub team_file: Option<String>,
+    pub tags: BTreeMap<String, String>,
+}
+
+pub fn get_config_path() -> String {
+    shellexpand::tilde("~/.config/cal2.toml").to_string()
+}
+
+impl Config {
+    pub fn load() -> Result<Self> {
+        Self::load_from(&get_config_path())
+    }
+
+    pub fn load_from(fname: &str) -> Result<Self> {
+        let contents = match fs::read_to_string(fname) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(err) => return Err(err.into()),
+        };
+
+        toml::from_str(&contents).map_err(|err| CalError::Config(format!("{fname}: {err}")))
+    }
+
+    pub fn fiscal_year_start(&self) -> Result<u32> {
+        match self.fiscal_year_start {
+            None => Ok(1),
+            Some(month @ 1..=12) => Ok(month),
+            Some(month) => Err(CalError::Config(format!(
+                "fiscal_year_start must be a month between 1 and 12, got {month}"
+            ))),
+        }
+    }
+
+    pub fn tag_colors(&self) -> Result<TagColors> {
+        self.tags
+            .iter()
+            .map(|(tag, color)| {
+                let wanted = color.trim().to_lowercase();
+                TagColor::ALL
+                    .into_iter()
+                    .find(|candidate| candidate.name() == wanted)
+                    .map(|color| (tag.to_lowercase(), color))
+                    .ok_or_else(|| {
+                        let names: Vec<&str> = TagColor::ALL.iter().map(|c| c.name()).collect();
+                        CalError::Config(format!(
+                            "unknown colour {color:?} for tag {tag:?}; use one of {}",
+                            names.join(", ")
+                        ))
+                    })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::SystemTime;
+
+    fn temp_file(label: &str) -> String {
+        let mut path = std::env::temp_dir();
+        let nanos = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .expect("time went backwards")
+            .as_nanos();
+        path.push(format!("cal2-config-{label}-{nanos}.toml"));
+        path.to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn load_from_missing_file_uses_defaults() {
+        let config = Config::load_from(&temp_file("missing")).expect("missing config is fine");
+        assert_eq!(config, Config::default());
+        assert_eq!(config.fiscal_year_start().expect("default start"), 1);
+    }
+
+    #[test]
+    fn load_from_reads_settings() {
+        let fname = temp_file("fiscal");
+        fs::write(
+            &fname,
+            "fiscal_year_start = 4\nlocale = \"es_AR\"\nlanguage = \"de\"\nteam_file = \"~/team/days.toml\"\n",
+        )
+        .expect("write config");
+
+        let config = Config::load_from(&fname).expect("config should parse");
+        assert_eq!(config.fiscal_year_start().expect("valid start"), 4);
+        assert_eq!(config.locale.as_deref(), Some("es_AR"));
+        assert_eq!(config.language.as_deref(), Some("de"));
+        assert_eq!(config.team_file.as_deref(), Some("~/team/days.toml"));
+
+        fs::remove_file(&fname).expect("remove temp config");
+    }
+
+    #[test]
+    fn load_from_reports_malformed_files() {
+        let fname = temp_file("malformed");
+        fs::write(&fname, "fiscal_year_start = \"april\"\n").expect("write config");
+
+        let err = Config::load_from(&fname).expect_err("config should be rejected");
+        assert!(matches!(err, CalError::Config(_)));
+
+        fs::remove_file(&fname).expect("remove temp config");
+    }
+
+    #[test]
+    fn tag_colors_are_validated() {
+        let fname = temp_file("tags");
+        fs::write(
+            &fname,
+            "[tags]\noncall = \"Magenta\"\nconference = \"blue\"\n",
+        )
+        .expect("write config");
+
+        let config = Config::load_from(&fname).expect("config should parse");
+        let colors = config.tag_colors().expect("valid colours");
+        assert_eq!(colors.get("oncall"), Some(&TagColor::Magenta));
+        assert_eq!(colors.get("conference"), Some(&TagColor::Blue));
+
+        let config = Config {
+            tags: [("oncall".to_string(), "mauve".to_string())].into(),
+            ..Config::default()
+        };
+        assert!(matches!(config.tag_colors(), Err(CalError::Config(_))));
+
+        fs::remove_file(&fname).expect("remove temp config");
+    }
+
+    #[test]
+    fn fiscal_year_start_rejects_invalid_months() {
+        let config = Config {
+            fiscal_year_start: Some(13),
+            ..Config::default()
+        };
+        assert!(config.fiscal_year_start().is_err());
+    }
+}
@@ -0,0 +1,316 @@
+use crate::error::{CalError, Result};
+use crate::locale;
+use chrono::{Datelike, Days, Locale, Month, NaiveDate, Weekday};
+
+pub fn parse(input: &str, today: NaiveDate, locale: Option<Locale>) -> Result<NaiveDate> {
+    parse_in_year(input, today, locale, today.year())
+}
+
+/// Like [`parse`], but dates written without a year (`24/12`) fall in `year`.
+pub fn parse_in_year(
+    input: &str,
+    today: NaiveDate,
+    locale: Option<Locale>,
+    year: i32,
+) -> Result<NaiveDate> {
+    let trimmed = input.trim();
+    let lower = trimmed.to_lowercase();
+    match lower.as_str() {
+        "today" => return Ok(today),
+        "tomorrow" => return shift(today, 1),
+        "yesterday" => return shift(today, -1),
+        "end of month" => {
+            return from_day_month(
+                last_day(today.year(), today.month()),
+                today.month(),
+                today.year(),
+            );
+        }
+        _ => {}
+    }
+    if let Some(offset) = parse_offset(&lower) {
+        return shift(today, offset);
+    }
+
+    if let Some(name) = lower.strip_prefix("next ") {
+        let weekday = parse_weekday(name.trim(), trimmed)?;
+        return upcoming(shift(today, 1)?, weekday);
+    }
+    if let Ok(weekday) = lower.parse::<Weekday>() {
+        return upcoming(today, weekday);
+    }
+
+    let numbers = |separator: char| -> Option<Vec<i32>> {
+        let parts: Vec<&str> = trimmed.split(separator).collect();
+        if !(2..=3).contains(&parts.len()) {
+            return None;
+        }
+        parts.iter().map(|part| part.trim().parse().ok()).collect()
+    };
+    if let Some(parts) = numbers('-')
+        && let [year, month, day] = parts[..]
+    {
+        return from_parts(day, month, year);
+    }
+    if let Some(mut parts) = numbers('/') {
+        if locale.is_some_and(locale::month_first) {
+            parts.swap(0, 1);
+        }
+        return match parts[..] {
+            [day, month] => from_parts(day, month, year),
+            [day, month, year] => from_parts(day, month, year),
+            _ => unreachable!("numbers() yields two or three parts"),
+        };
+    }
+
+    Err(CalError::InvalidDate(format!(
+        "{trimmed:?} is not a date; try 2026-12-24, 24/12, today, +3d, next friday or end of month"
+    )))
+}
+
+fn parse_offset(input: &str) -> Option<i64> {
+    let sign = match input.chars().next()? {
+        '+' => 1,
+        '-' => -1,
+        _ => return None,
+    };
+    let rest = &input[1..];
+    let (count, per_unit) = if let Some(count) = rest.strip_suffix('d') {
+        (count, 1)
+    } else if let Some(count) = rest.strip_suffix('w') {
+        (count, 7)
+    } else {
+        return None;
+    };
+    if count.is_empty() || !count.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let count: i64 = count.parse().ok()?;
+    Some(sign * count.checked_mul(per_unit)?)
+}
+
+pub fn from_day_month(day: u32, month: u32, year: i32) -> Result<NaiveDate> {
+    if let Some(date) = NaiveDate::from_ymd_opt(year, month, day) {
+        return Ok(date);
+    }
+
+    let given = format!("{day:02}/{month:02}/{year}");
+    let Some(name) = u8::try_from(month)
+        .ok()
+        .and_then(|month| Month::try_from(month).ok())
+    else {
+        let hint = if (1..=12).contains(&day) && (1..=31).contains(&month) {
+            format!("; did you mean {month:02}/{day:02}?")
+        } else {
+            String::new()
+        };
+        return Err(CalError::InvalidDate(format!(
+            "{given}: month must be between 1 and 12{hint}"
+        )));
+    };
+    if NaiveDate::from_ymd_opt(year, month, 1).is_none() {
+        return Err(CalError::InvalidDate(format!("{given}: year out of range")));
+    }
+    let last = last_day(year, month);
+    let hint = if day == 0 {
+        "days start at 1".to_string()
+    } else {
+        format!(
+            "{} {year} has {last} days; did you mean {last:02}/{month:02}?",
+            name.name()
+        )
+    };
+    Err(CalError::InvalidDate(format!("{given}: {hint}")))
+}
+
+fn from_parts(day: i32, month: i32, year: i32) -> Result<NaiveDate> {
+    match (u32::try_from(day), u32::try_from(month)) {
+        (Ok(day), Ok(month)) => from_day_month(day, month, year),
+        _ => Err(CalError::InvalidDate(format!(
+            "{day}/{month}/{year}: day and month cannot be negative"
+        ))),
+    }
+}
+
+fn last_day(year: i32, month: u32) -> u32 {
+    NaiveDate::from_ymd_opt(year, month, 1)
+        .and_then(|first| first.checked_add_months(chrono::Months::new(1)))
+        .and_then(|next| next.pred_opt())
+        .map(|last| last.day())
+        .unwrap_or(31)
+}
+
+fn parse_weekday(name: &str, input: &str) -> Result<Weekday> {
+    name.parse().map_err(|_| {
+        CalError::InvalidDate(format!(
+            "{input:?}: {name:?} is not a weekday; try next monday"
+        ))
+    })
+}
+
+fn upcoming(from: NaiveDate, weekday: Weekday) -> Result<NaiveDate> {
+    let ahead = (7 + weekday.num_days_from_monday() - from.weekday().num_days_from_monday()) % 7;
+    shift(from, ahead.into())
+}
+
+fn shift(date: NaiveDate, days: i64) -> Result<NaiveDate> {
+    let shifted = if days >= 0 {
+        date.checked_add_days(Days::new(days.unsigned_abs()))
+    } else {
+        date.checked_sub_days(Days::new(days.unsigned_abs()))
+    };
+    shifted.ok_or_else(|| CalError::InvalidDate(format!("{date} {days:+} days is out of range")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).expect("valid date")
+    }
+
+    fn message(result: Result<NaiveDate>) -> String {
+        match result {
+            Err(CalError::InvalidDate(message)) => message,
+            other => panic!("expected an invalid date, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn parse_accepts_iso_and_day_month() {
+        let today = date(2026, 10, 18);
+        assert_eq!(
+            parse("2027-01-06", today, None).expect("iso date"),
+            date(2027, 1, 6)
+        );
+        assert_eq!(
+            parse(" 24/12 ", today, None).expect("day/month"),
+            date(2026, 12, 24)
+        );
+        assert_eq!(
+            parse("6/1/2027", today, None).expect("day/month/year"),
+            date(2027, 1, 6)
+        );
+    }
+
+    #[test]
+    fn parse_understands_relative_words() {
+        let today = date(2026, 10, 18);
+        assert_eq!(parse("Today", today, None).expect("today"), today);
+        assert_eq!(
+            parse("tomorrow", today, None).expect("tomorrow"),
+            date(2026, 10, 19)
+        );
+        assert_eq!(
+            parse("friday", today, None).expect("weekday"),
+            date(2026, 10, 23)
+        );
+        assert_eq!(parse("sunday", today, None).expect("weekday"), today);
+        assert_eq!(
+            parse("next sunday", today, None).expect("next weekday"),
+            date(2026, 10, 25)
+        );
+        assert_eq!(
+            parse("next fri", today, None).expect("next weekday"),
+            date(2026, 10, 23)
+        );
+    }
+
+    #[test]
+    fn parse_understands_offsets_and_month_end() {
+        let today = date(2026, 2, 10);
+        assert_eq!(
+            parse("+3d", today, None).expect("offset"),
+            date(2026, 2, 13)
+        );
+        assert_eq!(
+            parse("+2w", today, None).expect("offset"),
+            date(2026, 2, 24)
+        );
+        assert_eq!(
+            parse("-10d", today, None).expect("offset"),
+            date(2026, 1, 31)
+        );
+        assert_eq!(
+            parse("yesterday", today, None).expect("yesterday"),
+            date(2026, 2, 9)
+        );
+        assert_eq!(
+            parse("End of month", today, None).expect("month end"),
+            date(2026, 2, 28)
+        );
+        for input in ["+d", "+3x", "3d", "+-3d", "+3é", "-é"] {
+            assert!(
+                matches!(parse(input, today, None), Err(CalError::InvalidDate(_))),
+                "{input:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn parse_in_year_only_moves_dates_without_a_year() {
+        let today = date(2026, 10, 18);
+        assert_eq!(
+            parse_in_year("24/12", today, None, 2027).expect("day month"),
+            date(2027, 12, 24)
+        );
+        assert_eq!(
+            parse_in_year("2028-01-02", today, None, 2027).expect("iso"),
+            date(2028, 1, 2)
+        );
+        assert_eq!(
+            parse_in_year("tomorrow", today, None, 2027).expect("relative"),
+            date(2026, 10, 19)
+        );
+    }
+
+    #[test]
+    fn parse_follows_locale_day_month_order() {
+        let today = date(2026, 10, 18);
+        assert_eq!(
+            parse("12/24", today, Some(Locale::en_US)).expect("month first"),
+            date(2026, 12, 24)
+        );
+        assert_eq!(
+            parse("24/12", today, Some(Locale::es_AR)).expect("day first"),
+            date(2026, 12, 24)
+        );
+        assert!(parse("24/12", today, Some(Locale::en_US)).is_err());
+        assert_eq!(
+            parse("2026-12-24", today, Some(Locale::en_US)).expect("iso"),
+            date(2026, 12, 24)
+        );
+    }
+
+    #[test]
+    fn parse_rejects_impossible_dates_with_suggestions() {
+        let today = date(2026, 10, 18);
+        assert_eq!(
+            message(parse("31/02", today, None)),
+            "31/02/2026: February 2026 has 28 days; did you mean 28/02?"
+        );
+        assert_eq!(
+            message(parse("2026-13-01", today, None)),
+            "01/13/2026: month must be between 1 and 12; did you mean 13/01?"
+        );
+        assert_eq!(
+            message(from_day_month(0, 13, 2026)),
+            "00/13/2026: month must be between 1 and 12"
+        );
+        assert_eq!(
+            message(from_day_month(0, 5, 2026)),
+            "00/05/2026: days start at 1"
+        );
+        assert_eq!(
+            message(from_day_month(24, 12, 999999)),
+            "24/12/999999: year out of range"
+        );
+        for input in ["soon", "", "next payday", "-1/5"] {
+            assert!(
+                matches!(parse(input, today, None), Err(CalError::InvalidDate(_))),
+                "expected invalid date for {input:?}"
+            );
+        }
+    }
+}
@@ -1,21 +1,43 @@
 use crate::{
     HM,
+    calendar::is_weekend,
+    config::{TagColor, TagColors},
     error::{CalError, Result},
+    holidays::{HolidayEntry, HolidayKind},
+    locale,
 };
-use chrono::{self, Datelike, Days, Month, NaiveDate, Weekday};
-use colored::Colorize;
+use chrono::{self, Datelike, Days, Locale, Month, NaiveDate};
+use colored::{ColoredString, Colorize};
 use prettytable::{Cell, Row, Table, format};
 
+pub const WIDTH: usize = 22;
+pub const WEEKDAYS: [&str; 7] = ["Mo", "Tu", "We", "Th", "Fr", "Sa", "Su"];
+
 #[derive(Clone)]
 pub struct DisplayMonth<'a> {
     pub month: u32,
-    pub month_name: String,
     pub year: i32,
+    pub month_name: String,
+    pub weekdays: Vec<String>,
+    pub locale: Locale,
+    countries: usize,
+    tag_colors: TagColors,
     first_day: NaiveDate,
     last_day: NaiveDate,
+    today: NaiveDate,
     hm: &'a HM,
 }
 
+#[derive(Clone, Debug)]
+pub struct Day<'a> {
+    pub date: NaiveDate,
+    pub weekend: bool,
+    pub today: bool,
+    pub shared: bool,
+    pub tag_color: Option<TagColor>,
+    pub holiday: Option<&'a HolidayEntry>,
+}
+
 impl<'a> DisplayMonth<'a> {
     pub fn new(month: u32, year: i32, hm: &'a HM) -> Result<Self> {
         let first_day = NaiveDate::from_ymd_opt(year, month, 1)
@@ -33,36 +55,43 @@ impl<'a> DisplayMonth<'a> {
             year,
             first_day,
             last_day,
+            today: chrono::Utc::now().naive_local().date(),
             month_name,
+            weekdays: WEEKDAYS.iter().map(|label| label.to_string()).collect(),
+            locale: Locale::POSIX,
+            countries: 1,
+            tag_colors: TagColors::new(),
             hm,
         })
     }
 
-    pub fn next(&self) -> Result<Self> {
-        let next_month = (self.month % 12) + 1;
-        let year = if next_month > self.month {
-            self.year
-        } else {
-            self.year + 1
-        };
-        Self::new(next_month, year, self.hm)
+    pub fn with_today(mut self, today: NaiveDate) -> Self {
+        self.today = today;
+        self
     }
 
-    pub fn prev(&self) -> Result<Self> {
-        let prev_month = if self.month == 1 { 12 } else { self.month - 1 };
-        let year = if prev_month < self.month {
-            self.year
-        } else {
-            self.year - 1
-        };
-        Self::new(prev_month, year, self.hm)
+    pub fn with_locale(mut self, locale: Locale) -> Self {
+        if let Some(title) = locale::month_title(self.month, self.year, locale) {
+            self.month_name = title;
+        }
+        self.weekdays = locale::weekday_labels(locale);
+        self.locale = locale;
+        self
     }
 
-    pub fn get_matrix(&self) -> Vec<Vec<String>> {
-        let today = chrono::Utc::now().naive_local().date();
+    pub fn with_countries(mut self, countries: usize) -> Self {
+        self.countries = countries;
+        self
+    }
+
+    pub fn with_tag_colors(mut self, tag_colors: TagColors) -> Self {
+        self.tag_colors = tag_colors;
+        self
+    }
+
+    pub fn weeks(&self) -> Vec<Vec<Option<Day<'a>>>> {
         let mut curr_day = self.first_day;
         let first_index = self.first_day.weekday().number_from_monday();
-        let weekends = [Weekday::Sat, Weekday::Sun];
         (1..self.last_day.day() + first_index)
             .map(|i| {
                 if i < first_index {
@@ -73,18 +102,25 @@ impl<'a> DisplayMonth<'a> {
                 if let Some(next_day) = curr_day.checked_add_days(Days::new(1)) {
                     curr_day = next_day;
                 }
-                let day = cr.day();
-                let is_holiday = self.hm.contains_key(&(day, self.month));
-                Some((cr, is_holiday))
-            })
-            .map(|x| match x {
-                Some((cr, _)) if cr == today => cr.day().to_string().black().on_white().to_string(),
-                Some((cr, _)) if weekends.contains(&cr.weekday()) => {
-                    cr.day().to_string().green().to_string()
-                }
-                Some((cr, true)) => cr.day().to_string().red().to_string(),
-                Some((cr, false)) => cr.day().to_string(),
-                None => String::new(),
+                let holiday = self
+                    .hm
+                    .get(&(cr.day(), self.month))
+                    .filter(|h| h.kind != HolidayKind::Worked);
+                Some(Day {
+                    date: cr,
+                    weekend: is_weekend(cr),
+                    today: cr == self.today,
+                    shared: self.countries > 1
+                        && holiday.is_some_and(|h| {
+                            h.kind == HolidayKind::Official && h.countries.len() >= self.countries
+                        }),
+                    tag_color: holiday.and_then(|h| {
+                        h.tags
+                            .iter()
+                            .find_map(|tag| self.tag_colors.get(tag).copied())
+                    }),
+                    holiday,
+                })
             })
             .collect::<Vec<_>>()
             .chunks(7)
@@ -92,8 +128,43 @@ impl<'a> DisplayMonth<'a> {
             .collect()
     }
 
+    pub fn get_matrix(&self) -> Vec<Vec<String>> {
+        self.weeks()
+            .into_iter()
+            .map(|week| {
+                week.into_iter()
+                    .map(|x| match x {
+                        Some(day) if day.today => {
+                            day.date.day().to_string().black().on_white().to_string()
+                        }
+                        Some(day) if day.shared => {
+                            day.date.day().to_string().white().on_red().to_string()
+                        }
+                        Some(day) if day.weekend => day.date.day().to_string().green().to_string(),
+                        Some(
+                            day @ Day {
+                                tag_color: Some(color),
+                                ..
+                            },
+                        ) => half_shade(&day, day.date.day().to_string().color(color.terminal())),
+                        Some(day) if day.holiday.is_some_and(|h| h.kind == HolidayKind::School) => {
+                            day.date.day().to_string().yellow().to_string()
+                        }
+                        Some(day) if day.holiday.is_some_and(|h| h.kind == HolidayKind::Leave) => {
+                            half_shade(&day, day.date.day().to_string().cyan())
+                        }
+                        Some(day) if day.holiday.is_some() => {
+                            half_shade(&day, day.date.day().to_string().red())
+                        }
+                        Some(day) => day.date.day().to_string(),
+                        None => String::new(),
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
     pub fn format(&self) -> String {
-        const WEEKDAYS: [&str; 7] = ["Mo", "Tu", "We", "Th", "Fr", "Sa", "Su"];
         let mut table = Table::new();
         let format = format::FormatBuilder::new()
             .column_separator(' ')
@@ -106,7 +177,7 @@ impl<'a> DisplayMonth<'a> {
             .build();
         table.set_format(format);
         table.add_row(Row::new(
-            WEEKDAYS
+            self.weekdays
                 .iter()
                 .map(|label| Cell::new(label))
                 .collect::<Vec<_>>(),
@@ -119,6 +190,14 @@ impl<'a> DisplayMonth<'a> {
     }
 }
 
+fn half_shade(day: &Day, text: ColoredString) -> String {
+    if day.holiday.is_some_and(|h| h.half_day) {
+        text.dimmed().to_string()
+    } else {
+        text.to_string()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -141,23 +220,51 @@ mod tests {
     }
 
     #[test]
-    fn prev_from_january_wraps_to_december_previous_year() {
-        let hm = HashMap::new();
-        let dm = DisplayMonth::new(1, 2024, &hm).expect("valid display month");
-        let prev = dm.prev().expect("previous month available");
-
-        assert_eq!(prev.month, 12);
-        assert_eq!(prev.year, 2023);
-    }
-
-    #[test]
-    fn next_from_december_wraps_to_january_next_year() {
-        let hm = HashMap::new();
-        let dm = DisplayMonth::new(12, 2023, &hm).expect("valid display month");
-        let next = dm.next().expect("next month available");
+    fn weeks_marks_days_every_country_observes() {
+        let mut hm = HashMap::new();
+        hm.insert(
+            (25, 12),
+            HolidayEntry {
+                countries: vec!["AR".to_string(), "DE".to_string()],
+                ..HolidayEntry::official("Christmas")
+            },
+        );
+        hm.insert(
+            (26, 12),
+            HolidayEntry {
+                countries: vec!["DE".to_string()],
+                ..HolidayEntry::official("Boxing Day")
+            },
+        );
+        hm.insert(
+            (31, 12),
+            HolidayEntry {
+                countries: vec!["AR".to_string(), "DE".to_string()],
+                ..HolidayEntry::custom("Team day")
+            },
+        );
+        let dm = DisplayMonth::new(12, 2026, &hm)
+            .expect("valid display month")
+            .with_countries(2);
 
-        assert_eq!(next.month, 1);
-        assert_eq!(next.year, 2024);
+        let shared: Vec<u32> = dm
+            .weeks()
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter(|day| day.shared)
+            .map(|day| day.date.day())
+            .collect();
+        assert_eq!(shared, [25]);
+        let single = DisplayMonth::new(12, 2026, &hm).expect("valid display month");
+        assert!(
+            single
+                .weeks()
+                .into_iter()
+                .flatten()
+                .flatten()
+                .all(|day| !day.shared)
+        );
     }
 
     #[test]
@@ -203,6 +310,103 @@ mod tests {
         );
     }
 
+    #[test]
+    fn get_matrix_colours_tagged_days() {
+        let _color_guard = ColorGuard::enable();
+        let mut hm = HashMap::new();
+        hm.insert(
+            (3, 12),
+            HolidayEntry {
+                tags: vec!["team".to_string(), "oncall".to_string()],
+                ..HolidayEntry::custom("Rotation")
+            },
+        );
+        hm.insert(
+            (4, 12),
+            HolidayEntry {
+                tags: vec!["untinted".to_string()],
+                ..HolidayEntry::custom("Other")
+            },
+        );
+        let colors: TagColors = [("oncall".to_string(), TagColor::Magenta)].into();
+        let dm = DisplayMonth::new(12, 2026, &hm)
+            .expect("valid display month")
+            .with_tag_colors(colors);
+
+        let weeks = dm.weeks();
+        let days: Vec<&Day> = weeks.iter().flatten().flatten().collect();
+        assert_eq!(days[2].tag_color, Some(TagColor::Magenta));
+        assert_eq!(days[3].tag_color, None);
+
+        let matrix = dm.get_matrix();
+        // December 2026 starts on a Tuesday.
+        assert!(matrix[0][3].contains("\u{1b}[35m"), "{:?}", matrix[0][3]);
+        assert!(matrix[0][4].contains("\u{1b}[31m"), "{:?}", matrix[0][4]);
+    }
+
+    #[test]
+    fn get_matrix_dims_half_days() {
+        let _color_guard = ColorGuard::enable();
+        let mut hm = HashMap::new();
+        hm.insert(
+            (24, 12),
+            HolidayEntry {
+                half_day: true,
+                ..HolidayEntry::custom("Christmas Eve")
+            },
+        );
+        hm.insert((25, 12), HolidayEntry::official("Christmas"));
+        let dm = DisplayMonth::new(12, 2026, &hm).expect("valid display month");
+
+        let matrix = dm.get_matrix();
+        let cell = |day: &str| {
+            matrix
+                .iter()
+                .flatten()
+                .find(|cell| cell.contains(&format!("m{day}\u{1b}")))
+                .cloned()
+                .unwrap_or_default()
+        };
+        assert!(cell("24").contains("\u{1b}[2;31m"), "{:?}", cell("24"));
+        assert!(cell("25").contains("\u{1b}[31m"), "{:?}", cell("25"));
+    }
+
+    #[test]
+    fn weeks_describe_each_day() {
+        let mut hm = HashMap::new();
+        hm.insert((1, 1), HolidayEntry::official("New Year's Day".to_string()));
+        let today = NaiveDate::from_ymd_opt(1970, 1, 2).expect("valid date");
+        let dm = DisplayMonth::new(1, 1970, &hm)
+            .expect("valid display month")
+            .with_today(today);
+
+        let weeks = dm.weeks();
+        assert!(weeks[0][0].is_none());
+        let first = weeks[0][3].as_ref().expect("January 1st is a Thursday");
+        assert_eq!(first.date.day(), 1);
+        assert_eq!(
+            first.holiday.map(|h| h.name.as_str()),
+            Some("New Year's Day")
+        );
+        assert!(!first.weekend);
+        assert!(weeks[0][4].as_ref().is_some_and(|day| day.today));
+        assert!(weeks[0][5].as_ref().is_some_and(|day| day.weekend));
+    }
+
+    #[test]
+    fn with_locale_translates_titles_and_headers() {
+        let hm = HashMap::new();
+        let dm = DisplayMonth::new(1, 2026, &hm)
+            .expect("valid display month")
+            .with_locale(Locale::es_AR);
+
+        assert_eq!(dm.month_name, "Enero 2026");
+        let formatted = dm.format();
+        assert!(formatted.contains("Lu"));
+        assert!(formatted.contains("Do"));
+        assert!(!formatted.contains("Mo"));
+    }
+
     #[test]
     fn format_includes_weekday_headers() {
         let _color_guard = ColorGuard::enable();
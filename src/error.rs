@@ -18,6 +18,10 @@ pub enum CalError {
     Config(String),
     #[error("cache error: {0}")]
     Cache(String),
+    #[error("template error: {0}")]
+    Template(String),
+    #[error("cannot edit entry: {0}")]
+    Entry(String),
 }
 
 pub type Result<T> = std::result::Result<T, CalError>;
@@ -2,9 +2,10 @@ use crate::{
     HM,
     error::{CalError, Result},
 };
-use serde::{Deserialize, Serialize};
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, BTreeSet, HashMap, hash_map::Entry},
     fs::{self, File},
     io::{self, BufWriter, Write},
 };
@@ -13,12 +14,40 @@ use std::{
 pub enum HolidayKind {
     Official,
     Custom,
+    School,
+    Leave,
+    Worked,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct HolidayEntry {
     pub name: String,
     pub kind: HolidayKind,
+    pub translations: BTreeMap<String, String>,
+    pub regions: Vec<String>,
+    pub half_day: bool,
+    pub tags: Vec<String>,
+    #[serde(skip)]
+    pub countries: Vec<String>,
+}
+
+impl HolidayKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            HolidayKind::Official => "official",
+            HolidayKind::Custom => "custom",
+            HolidayKind::School => "school",
+            HolidayKind::Leave => "leave",
+            HolidayKind::Worked => "worked",
+        }
+    }
+
+    pub fn is_day_off(self) -> bool {
+        matches!(
+            self,
+            HolidayKind::Official | HolidayKind::Custom | HolidayKind::Leave
+        )
+    }
 }
 
 impl HolidayEntry {
@@ -26,6 +55,11 @@ impl HolidayEntry {
         Self {
             name: name.into(),
             kind: HolidayKind::Official,
+            translations: BTreeMap::new(),
+            regions: Vec::new(),
+            half_day: false,
+            tags: Vec::new(),
+            countries: Vec::new(),
         }
     }
 
@@ -33,10 +67,66 @@ impl HolidayEntry {
         Self {
             name: name.into(),
             kind: HolidayKind::Custom,
+            translations: BTreeMap::new(),
+            regions: Vec::new(),
+            half_day: false,
+            tags: Vec::new(),
+            countries: Vec::new(),
+        }
+    }
+
+    pub fn in_language(mut self, language: &str) -> Self {
+        if let Some(text) = self.translations.get(language) {
+            self.name = text.clone();
         }
+        self
+    }
+
+    pub fn school(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            kind: HolidayKind::School,
+            translations: BTreeMap::new(),
+            regions: Vec::new(),
+            half_day: false,
+            tags: Vec::new(),
+            countries: Vec::new(),
+        }
+    }
+
+    pub fn has_any_tag(&self, tags: &[String]) -> bool {
+        self.tags.iter().any(|tag| tags.contains(tag))
+    }
+
+    /// Share of the day taken off: 1 for a full day off, 0.5 for a half day.
+    pub fn day_off(&self) -> f64 {
+        match (self.kind.is_day_off(), self.half_day) {
+            (false, _) => 0.0,
+            (true, true) => 0.5,
+            (true, false) => 1.0,
+        }
+    }
+
+    pub fn applies_to(&self, region: &str) -> bool {
+        self.regions.is_empty()
+            || self.regions.iter().any(|code| {
+                code == region
+                    || code.starts_with(&format!("{region}-"))
+                    || region.starts_with(&format!("{code}-"))
+            })
     }
 }
 
+pub fn parse_language(language: &str) -> Result<String> {
+    let upper = language.trim().to_uppercase();
+    if upper.len() != 2 || !upper.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Err(CalError::Config(format!(
+            "unknown language {language:?}; use a 2-letter ISO code such as DE or ES"
+        )));
+    }
+    Ok(upper)
+}
+
 #[derive(Debug, Deserialize)]
 struct ArgentinaResp {
     fecha: String,
@@ -49,6 +139,27 @@ struct OpenHolidayResp {
     #[serde(rename = "startDate")]
     start_date: String,
     name: Vec<OpenHolidayName>,
+    #[serde(default = "nationwide_default")]
+    nationwide: bool,
+    #[serde(default)]
+    subdivisions: Vec<OpenHolidaySubdivision>,
+}
+
+fn nationwide_default() -> bool {
+    true
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenHolidaySubdivision {
+    code: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SchoolHolidayResp {
+    start_date: String,
+    end_date: String,
+    name: Vec<OpenHolidayName>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -63,6 +174,7 @@ pub enum Provider {
     ArgentinaDatos,
     OpenHolidays {
         country_code: String,
+        region: Option<String>,
     },
 }
 
@@ -95,18 +207,101 @@ impl Provider {
         } else {
             Ok(Provider::OpenHolidays {
                 country_code: upper,
+                region: None,
             })
         }
     }
 
+    pub fn from_args(country: Option<String>, region: Option<String>) -> Result<Self> {
+        let Some(region) = region else {
+            return Self::from_country(country);
+        };
+
+        let upper = region.trim().to_uppercase();
+        let valid = match upper.split_once('-') {
+            Some((prefix, rest)) => {
+                (2..=3).contains(&prefix.len())
+                    && prefix.chars().all(|c| c.is_ascii_alphabetic())
+                    && !rest.is_empty()
+                    && rest.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+            }
+            None => false,
+        };
+        if !valid {
+            return Err(CalError::Config(format!(
+                "--region must look like DE-BY, got {region:?}"
+            )));
+        }
+
+        let prefix = upper.split('-').next().unwrap_or_default().to_string();
+        match Self::from_country(Some(country.unwrap_or_else(|| prefix.clone())))? {
+            Provider::OpenHolidays { country_code, .. } if country_code == prefix => {
+                Ok(Provider::OpenHolidays {
+                    country_code,
+                    region: Some(upper),
+                })
+            }
+            Provider::OpenHolidays { country_code, .. } => Err(CalError::Config(format!(
+                "--region {upper} does not belong to --country {country_code}"
+            ))),
+            Provider::ArgentinaDatos => Err(CalError::Config(
+                "--region is only supported for OpenHolidays countries".to_string(),
+            )),
+        }
+    }
+
+    pub fn list_from_args(country: Option<String>, region: Option<String>) -> Result<Vec<Self>> {
+        let codes: Vec<String> = match &country {
+            Some(country) if country.contains(',') => {
+                country.split(',').map(str::to_string).collect()
+            }
+            _ => return Ok(vec![Self::from_args(country, region)?]),
+        };
+        if region.is_some() {
+            return Err(CalError::Config(
+                "--region cannot be combined with several countries".to_string(),
+            ));
+        }
+
+        let mut providers: Vec<Self> = Vec::with_capacity(codes.len());
+        for code in codes {
+            let provider = Self::from_country(Some(code))?;
+            if providers
+                .iter()
+                .any(|p| p.country_code() == provider.country_code())
+            {
+                return Err(CalError::Config(format!(
+                    "--country lists {} more than once",
+                    provider.country_code()
+                )));
+            }
+            providers.push(provider);
+        }
+        Ok(providers)
+    }
+
+    pub fn country_code(&self) -> &str {
+        match self {
+            Provider::ArgentinaDatos => "AR",
+            Provider::OpenHolidays { country_code, .. } => country_code,
+        }
+    }
+
+    pub fn region(&self) -> Option<&str> {
+        match self {
+            Provider::ArgentinaDatos => None,
+            Provider::OpenHolidays { region, .. } => region.as_deref(),
+        }
+    }
+
     fn is_default(&self) -> bool {
         matches!(self, Provider::ArgentinaDatos)
     }
 
-    fn slug(&self) -> String {
+    pub fn slug(&self) -> String {
         match self {
             Provider::ArgentinaDatos => "argentina-datos".to_string(),
-            Provider::OpenHolidays { country_code } => {
+            Provider::OpenHolidays { country_code, .. } => {
                 format!("openholidays-{}", country_code.to_lowercase())
             }
         }
@@ -115,7 +310,7 @@ impl Provider {
     fn fetch(&self, year: i32) -> Result<HM> {
         match self {
             Provider::ArgentinaDatos => fetch_argentina(year),
-            Provider::OpenHolidays { country_code } => fetch_openholidays(year, country_code),
+            Provider::OpenHolidays { country_code, .. } => fetch_openholidays(year, country_code),
         }
     }
 }
@@ -129,9 +324,190 @@ pub fn get_filename(year: i32, provider: &Provider) -> String {
     shellexpand::tilde(&format!("~/.config/{basename}")).to_string()
 }
 
+pub fn get_person_filename(year: i32, provider: &Provider, person: &str) -> String {
+    let basename = format!("hm-person-{}-{year}-{person}", provider.slug());
+    shellexpand::tilde(&format!("~/.config/{basename}")).to_string()
+}
+
+/// Names of the people owning a cache file whose name starts with one of
+/// `prefixes`; the person is the rest of the file name.
+pub fn list_people(prefixes: &[String]) -> Result<Vec<String>> {
+    let dir = shellexpand::tilde("~/.config").to_string();
+    let entries = match fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err.into()),
+    };
+    let mut people = BTreeSet::new();
+    for entry in entries {
+        let name = entry?.file_name();
+        let Some(name) = name.to_str() else {
+            continue;
+        };
+        if let Some(person) = prefixes
+            .iter()
+            .find_map(|prefix| name.strip_prefix(prefix.as_str()))
+            && !person.is_empty()
+        {
+            people.insert(person.to_string());
+        }
+    }
+    Ok(people.into_iter().collect())
+}
+
+pub type Hidden = BTreeSet<(u32, u32)>;
+
+pub fn get_custom_filename(year: i32, provider: &Provider) -> String {
+    let basename = format!("hm-custom-{}-{year}", provider.slug());
+    shellexpand::tilde(&format!("~/.config/{basename}")).to_string()
+}
+
+pub fn get_hidden_filename(year: i32, provider: &Provider) -> String {
+    let basename = format!("hm-hidden-{}-{year}", provider.slug());
+    shellexpand::tilde(&format!("~/.config/{basename}")).to_string()
+}
+
+pub fn load_hidden(fname: &str) -> Result<Hidden> {
+    match fs::read(fname) {
+        Ok(bytes) => bincode::deserialize(&bytes)
+            .map_err(|_| CalError::Cache(format!("failed to deserialize overrides {fname}"))),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Hidden::new()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+pub fn save_hidden(fname: &str, hidden: &Hidden) -> Result<()> {
+    let file = File::create(fname)?;
+    let mut writer = BufWriter::new(file);
+    bincode::serialize_into(&mut writer, hidden)?;
+    writer.flush()?;
+    Ok(())
+}
+
+pub fn apply_hidden(hm: &mut HM, hidden: &Hidden) {
+    for key in hidden {
+        if let Some(entry) = hm.get_mut(key)
+            && entry.kind == HolidayKind::Official
+        {
+            entry.kind = HolidayKind::Worked;
+        }
+    }
+}
+
+pub fn get_school_filename(year: i32, region: &str) -> String {
+    let basename = format!("hm-school-{}-{year}", region.to_lowercase());
+    shellexpand::tilde(&format!("~/.config/{basename}")).to_string()
+}
+
 type LegacyHM = HashMap<(u32, u32), bool>;
+
+#[derive(Deserialize)]
+struct HolidayEntryV1 {
+    name: String,
+    kind: HolidayKind,
+}
+
+impl From<HolidayEntryV1> for HolidayEntry {
+    fn from(entry: HolidayEntryV1) -> Self {
+        Self {
+            name: entry.name,
+            kind: entry.kind,
+            translations: BTreeMap::new(),
+            regions: Vec::new(),
+            half_day: false,
+            tags: Vec::new(),
+            countries: Vec::new(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct HolidayEntryV2 {
+    name: String,
+    kind: HolidayKind,
+    translations: BTreeMap<String, String>,
+}
+
+impl From<HolidayEntryV2> for HolidayEntry {
+    fn from(entry: HolidayEntryV2) -> Self {
+        Self {
+            name: entry.name,
+            kind: entry.kind,
+            translations: entry.translations,
+            regions: Vec::new(),
+            half_day: false,
+            tags: Vec::new(),
+            countries: Vec::new(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct HolidayEntryV3 {
+    name: String,
+    kind: HolidayKind,
+    translations: BTreeMap<String, String>,
+    regions: Vec<String>,
+}
+
+impl From<HolidayEntryV3> for HolidayEntry {
+    fn from(entry: HolidayEntryV3) -> Self {
+        Self {
+            name: entry.name,
+            kind: entry.kind,
+            translations: entry.translations,
+            regions: entry.regions,
+            half_day: false,
+            tags: Vec::new(),
+            countries: Vec::new(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct HolidayEntryV4 {
+    name: String,
+    kind: HolidayKind,
+    translations: BTreeMap<String, String>,
+    regions: Vec<String>,
+    half_day: bool,
+}
+
+impl From<HolidayEntryV4> for HolidayEntry {
+    fn from(entry: HolidayEntryV4) -> Self {
+        Self {
+            name: entry.name,
+            kind: entry.kind,
+            translations: entry.translations,
+            regions: entry.regions,
+            half_day: entry.half_day,
+            tags: Vec::new(),
+            countries: Vec::new(),
+        }
+    }
+}
+
+fn migrate<T>(bytes: &[u8]) -> Option<HM>
+where
+    T: DeserializeOwned + Into<HolidayEntry>,
+{
+    let old: HashMap<(u32, u32), T> = bincode::deserialize(bytes).ok()?;
+    Some(
+        old.into_iter()
+            .map(|(key, entry)| (key, entry.into()))
+            .collect(),
+    )
+}
+
 const MAX_CACHE_BYTES: u64 = 10 * 1024 * 1024;
 
+/// Caches start with this tag and a format version byte. Changing the
+/// `HolidayEntry` layout bumps `CACHE_VERSION` and adds a match arm in `load`
+/// for the previous one. Versions 1-4 are the `HolidayEntryV*` layouts, which
+/// were written without a header.
+const CACHE_MAGIC: &[u8] = b"cal2";
+const CACHE_VERSION: u8 = 5;
+
 pub fn load(fname: &str) -> Result<Option<HM>> {
     let metadata = match fs::metadata(fname) {
         Ok(meta) => meta,
@@ -146,31 +522,54 @@ pub fn load(fname: &str) -> Result<Option<HM>> {
     }
 
     let bytes = fs::read(fname)?;
+    let corrupt = || CalError::Cache(format!("failed to deserialize cache {fname}"));
 
-    if let Ok(resp) = bincode::deserialize::<HM>(&bytes) {
-        return Ok(Some(resp));
-    }
-
-    if let Ok(legacy) = bincode::deserialize::<LegacyHM>(&bytes) {
-        let mut migrated = HashMap::new();
-        for ((day, month), is_holiday) in legacy {
-            if is_holiday {
-                let name = format!("Legacy holiday ({day:02}/{month:02})");
-                migrated.insert((day, month), HolidayEntry::custom(name));
-            }
-        }
+    let Some(rest) = bytes.strip_prefix(CACHE_MAGIC) else {
+        let migrated = load_headerless(&bytes).ok_or_else(corrupt)?;
         save(fname, &migrated)?;
         return Ok(Some(migrated));
+    };
+    match rest.split_first() {
+        Some((&CACHE_VERSION, body)) => bincode::deserialize(body).map(Some).map_err(|_| corrupt()),
+        Some((version, _)) => Err(CalError::Cache(format!(
+            "cache {fname} has format version {version}; this cal2 reads version {CACHE_VERSION}"
+        ))),
+        None => Err(corrupt()),
     }
+}
 
-    Err(CalError::Cache(format!(
-        "failed to deserialize cache {fname}"
-    )))
+/// Caches written before the version header. The layouts are tried newest
+/// first; bincode accepts trailing bytes, so an older layout would also match
+/// a newer cache. This list is closed: new layouts get a `CACHE_VERSION`.
+fn load_headerless(bytes: &[u8]) -> Option<HM> {
+    if let Ok(hm) = bincode::deserialize::<HM>(bytes) {
+        return Some(hm);
+    }
+    if let Some(migrated) = migrate::<HolidayEntryV4>(bytes)
+        .or_else(|| migrate::<HolidayEntryV3>(bytes))
+        .or_else(|| migrate::<HolidayEntryV2>(bytes))
+        .or_else(|| migrate::<HolidayEntryV1>(bytes))
+    {
+        return Some(migrated);
+    }
+    let legacy = bincode::deserialize::<LegacyHM>(bytes).ok()?;
+    Some(
+        legacy
+            .into_iter()
+            .filter(|(_, is_holiday)| *is_holiday)
+            .map(|((day, month), _)| {
+                let name = format!("Legacy holiday ({day:02}/{month:02})");
+                ((day, month), HolidayEntry::custom(name))
+            })
+            .collect(),
+    )
 }
 
 pub fn save(fname: &str, hm: &HM) -> Result<()> {
     let file = File::create(fname)?;
     let mut writer = BufWriter::new(file);
+    writer.write_all(CACHE_MAGIC)?;
+    writer.write_all(&[CACHE_VERSION])?;
     bincode::serialize_into(&mut writer, hm)?;
     writer.flush()?;
     Ok(())
@@ -187,43 +586,193 @@ pub fn get_holidays(year: i32, provider: &Provider) -> Result<HM> {
     Ok(hm)
 }
 
+/// Custom and leave entries of `year`. They used to be stored in the
+/// provider cache, which then never refetched the official holidays; any
+/// found there are moved to their own file on first use.
+pub fn load_custom(year: i32, provider: &Provider) -> Result<HM> {
+    let fname = get_custom_filename(year, provider);
+    if let Some(hm) = load(&fname)? {
+        return Ok(hm);
+    }
+
+    let cache = get_filename(year, provider);
+    let Some(cached) = load(&cache)? else {
+        return Ok(HM::new());
+    };
+    let (custom, fetched): (HM, HM) = cached
+        .into_iter()
+        .partition(|(_, entry)| matches!(entry.kind, HolidayKind::Custom | HolidayKind::Leave));
+    if !custom.is_empty() {
+        save(&fname, &custom)?;
+        if fetched.is_empty() {
+            fs::remove_file(&cache)?;
+        } else {
+            save(&cache, &fetched)?;
+        }
+    }
+    Ok(custom)
+}
+
+pub fn get_school_holidays(year: i32, provider: &Provider) -> Result<HM> {
+    let (Provider::OpenHolidays { country_code, .. }, Some(region)) = (provider, provider.region())
+    else {
+        return Err(CalError::Config(
+            "--include school requires --region, e.g. --region ES-MD".to_string(),
+        ));
+    };
+
+    let fname = get_school_filename(year, region);
+    if let Some(hm) = load(&fname)? {
+        return Ok(hm);
+    }
+
+    let hm = fetch_school_holidays(year, country_code, region)?;
+    save(&fname, &hm)?;
+    Ok(hm)
+}
+
+pub fn merge(sets: Vec<(String, HM)>) -> HM {
+    let mut merged = HM::new();
+    for (country, hm) in sets {
+        for (key, mut entry) in hm {
+            if entry.kind == HolidayKind::Worked {
+                continue;
+            }
+            match merged.entry(key) {
+                Entry::Occupied(occupied) => {
+                    let existing = occupied.into_mut();
+                    if !existing.name.split(" / ").any(|name| name == entry.name) {
+                        existing.name = format!("{} / {}", existing.name, entry.name);
+                    }
+                    // Countries only count towards an official holiday when
+                    // they observe it officially, not through a custom day.
+                    if entry.kind == HolidayKind::Official && existing.kind != entry.kind {
+                        existing.kind = HolidayKind::Official;
+                        existing.countries.clear();
+                    }
+                    let official = existing.kind == HolidayKind::Official;
+                    if (entry.kind == HolidayKind::Official || !official)
+                        && !existing.countries.contains(&country)
+                    {
+                        existing.countries.push(country.clone());
+                    }
+                }
+                Entry::Vacant(vacant) => {
+                    entry.countries = vec![country.clone()];
+                    vacant.insert(entry);
+                }
+            }
+        }
+    }
+    merged
+}
+
 fn fetch_argentina(year: i32) -> Result<HM> {
     let response =
         reqwest::blocking::get(format!("https://api.argentinadatos.com/v1/feriados/{year}"))?;
     let data = response.text()?;
     let entries: Vec<ArgentinaResp> = serde_json::from_str(&data)?;
-    Ok(build_holidays(
-        entries.into_iter().map(|resp| (resp.fecha, resp.nombre)),
-    ))
+    Ok(build_holidays(entries.into_iter().map(|resp| {
+        (resp.fecha, HolidayEntry::official(resp.nombre))
+    })))
 }
 
 fn fetch_openholidays(year: i32, country_code: &str) -> Result<HM> {
     let url = format!(
-        "https://openholidaysapi.org/PublicHolidays?countryIsoCode={country_code}&languageIsoCode=EN&validFrom={year}-01-01&validTo={year}-12-31"
+        "https://openholidaysapi.org/PublicHolidays?countryIsoCode={country_code}&validFrom={year}-01-01&validTo={year}-12-31"
     );
     let response = reqwest::blocking::get(url)?;
     let data = response.text()?;
     let entries: Vec<OpenHolidayResp> = serde_json::from_str(&data)?;
-    Ok(build_holidays(entries.into_iter().map(|resp| {
-        let chosen = resp
-            .name
-            .iter()
-            .find(|n| n.language.eq_ignore_ascii_case("EN"))
-            .or_else(|| resp.name.first())
-            .map(|n| n.text.clone())
-            .unwrap_or_else(|| "Public holiday".to_string());
-        (resp.start_date, chosen)
-    })))
+    Ok(build_holidays(entries.into_iter().map(openholidays_entry)))
+}
+
+fn fetch_school_holidays(year: i32, country_code: &str, region: &str) -> Result<HM> {
+    let url = format!(
+        "https://openholidaysapi.org/SchoolHolidays?countryIsoCode={country_code}&subdivisionCode={region}&validFrom={year}-01-01&validTo={year}-12-31"
+    );
+    let response = reqwest::blocking::get(url)?;
+    let data = response.text()?;
+    let entries: Vec<SchoolHolidayResp> = serde_json::from_str(&data)?;
+    Ok(build_school_holidays(year, entries))
+}
+
+fn build_school_holidays(year: i32, entries: Vec<SchoolHolidayResp>) -> HM {
+    let mut hm = HashMap::new();
+    for resp in entries {
+        let parse = |date: &str| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok();
+        let (Some(start), Some(end)) = (parse(&resp.start_date), parse(&resp.end_date)) else {
+            continue;
+        };
+        let (name, translations) = names(resp.name, "School holidays");
+        let entry = HolidayEntry {
+            translations,
+            ..HolidayEntry::school(name)
+        };
+        for date in start.iter_days().take_while(|date| *date <= end) {
+            if date.year() == year {
+                hm.insert((date.day(), date.month()), entry.clone());
+            }
+        }
+    }
+    hm
+}
+
+fn names(names: Vec<OpenHolidayName>, fallback: &str) -> (String, BTreeMap<String, String>) {
+    let translations: BTreeMap<String, String> = names
+        .into_iter()
+        .map(|n| (n.language.to_uppercase(), n.text))
+        .collect();
+    let name = translations
+        .get("EN")
+        .or_else(|| translations.values().next())
+        .cloned()
+        .unwrap_or_else(|| fallback.to_string());
+    (name, translations)
+}
+
+fn openholidays_entry(resp: OpenHolidayResp) -> (String, HolidayEntry) {
+    let (name, translations) = names(resp.name, "Public holiday");
+    let regions = if resp.nationwide {
+        Vec::new()
+    } else {
+        resp.subdivisions.into_iter().map(|s| s.code).collect()
+    };
+    let entry = HolidayEntry {
+        translations,
+        regions,
+        ..HolidayEntry::official(name)
+    };
+    (resp.start_date, entry)
 }
 
 fn build_holidays<I>(entries: I) -> HM
 where
-    I: IntoIterator<Item = (String, String)>,
+    I: IntoIterator<Item = (String, HolidayEntry)>,
 {
     let mut hm = HashMap::new();
-    for (date, name) in entries {
-        if let Some((day, month)) = parse_date(&date) {
-            hm.insert((day, month), HolidayEntry::official(name));
+    for (date, entry) in entries {
+        let Some(key) = parse_date(&date) else {
+            continue;
+        };
+        match hm.entry(key) {
+            Entry::Vacant(vacant) => {
+                vacant.insert(entry);
+            }
+            // Regional holidays on the same date are merged so that no region
+            // loses its day; a nationwide holiday covers every region anyway.
+            Entry::Occupied(mut occupied) => {
+                let existing = occupied.get_mut();
+                if entry.regions.is_empty() {
+                    *existing = entry;
+                } else if !existing.regions.is_empty() {
+                    for region in entry.regions {
+                        if !existing.regions.contains(&region) {
+                            existing.regions.push(region);
+                        }
+                    }
+                }
+            }
         }
     }
     hm
@@ -278,7 +827,8 @@ mod tests {
         let fname = temp_file("roundtrip");
         save(&fname, &hm).expect("save should succeed");
         let raw_bytes = fs::read(&fname).expect("able to read serialized data");
-        let raw_result: std::result::Result<HM, _> = bincode::deserialize(&raw_bytes);
+        assert_eq!(raw_bytes[..5], *b"cal2\x05");
+        let raw_result: std::result::Result<HM, _> = bincode::deserialize(&raw_bytes[5..]);
         assert!(
             raw_result.is_ok(),
             "raw deserialize failed: {:?}",
@@ -329,6 +879,381 @@ mod tests {
         fs::remove_file(&legacy_fname).expect("remove migrated cache");
     }
 
+    #[test]
+    fn load_migrates_caches_without_translations() {
+        #[derive(Serialize)]
+        struct V1 {
+            name: String,
+            kind: HolidayKind,
+        }
+
+        let fname = temp_file("v1");
+        let mut v1 = HashMap::new();
+        v1.insert(
+            (25, 12),
+            V1 {
+                name: "Christmas Day".to_string(),
+                kind: HolidayKind::Official,
+            },
+        );
+        {
+            let mut file = File::create(&fname).expect("create v1 file");
+            bincode::serialize_into(&mut file, &v1).expect("serialize v1 cache");
+        }
+
+        let migrated = load(&fname)
+            .expect("v1 cache should migrate")
+            .expect("migrated cache should exist");
+        assert_eq!(
+            migrated.get(&(25, 12)),
+            Some(&HolidayEntry::official("Christmas Day"))
+        );
+        let raw_bytes = fs::read(&fname).expect("read migrated cache");
+        assert!(raw_bytes.starts_with(CACHE_MAGIC));
+        assert!(bincode::deserialize::<HM>(&raw_bytes[5..]).is_ok());
+
+        fs::remove_file(&fname).expect("remove migrated cache");
+    }
+
+    #[test]
+    fn load_adds_the_version_header_to_headerless_caches() {
+        let fname = temp_file("headerless");
+        let mut hm = HM::new();
+        hm.insert((25, 12), HolidayEntry::official("Christmas Day"));
+        fs::write(&fname, bincode::serialize(&hm).expect("serialize")).expect("write cache");
+
+        assert_eq!(load(&fname).expect("load").expect("cache"), hm);
+        let raw_bytes = fs::read(&fname).expect("read rewritten cache");
+        assert_eq!(raw_bytes[..5], *b"cal2\x05");
+        assert_eq!(load(&fname).expect("reload").expect("cache"), hm);
+
+        fs::remove_file(&fname).expect("remove cache");
+    }
+
+    #[test]
+    fn load_rejects_unknown_cache_versions() {
+        let fname = temp_file("future");
+        fs::write(&fname, b"cal2\x06").expect("write cache");
+        assert!(matches!(load(&fname), Err(CalError::Cache(_))));
+        fs::remove_file(&fname).expect("remove cache");
+    }
+
+    #[test]
+    fn openholidays_entry_keeps_all_translations() {
+        let resp: OpenHolidayResp = serde_json::from_str(
+            r#"{"startDate":"2026-10-03","name":[{"language":"DE","text":"Tag der Deutschen Einheit"},{"language":"EN","text":"German Unity Day"}]}"#,
+        )
+        .expect("valid response");
+
+        let (date, entry) = openholidays_entry(resp);
+        assert_eq!(date, "2026-10-03");
+        assert_eq!(entry.name, "German Unity Day");
+        assert_eq!(entry.translations.len(), 2);
+        assert_eq!(
+            entry.clone().in_language("DE").name,
+            "Tag der Deutschen Einheit"
+        );
+        assert_eq!(entry.in_language("FR").name, "German Unity Day");
+    }
+
+    #[test]
+    fn load_migrates_caches_without_regions() {
+        #[derive(Serialize)]
+        struct V2 {
+            name: String,
+            kind: HolidayKind,
+            translations: BTreeMap<String, String>,
+        }
+
+        let fname = temp_file("v2");
+        let mut translations = BTreeMap::new();
+        translations.insert("DE".to_string(), "Neujahr".to_string());
+        let mut v2 = HashMap::new();
+        v2.insert(
+            (1, 1),
+            V2 {
+                name: "New Year's Day".to_string(),
+                kind: HolidayKind::Official,
+                translations: translations.clone(),
+            },
+        );
+        {
+            let mut file = File::create(&fname).expect("create v2 file");
+            bincode::serialize_into(&mut file, &v2).expect("serialize v2 cache");
+        }
+
+        let migrated = load(&fname)
+            .expect("v2 cache should migrate")
+            .expect("migrated cache should exist");
+        let entry = migrated.get(&(1, 1)).expect("entry survives migration");
+        assert_eq!(entry.translations, translations);
+        assert!(entry.regions.is_empty());
+
+        fs::remove_file(&fname).expect("remove migrated cache");
+    }
+
+    #[test]
+    fn load_migrates_caches_without_half_days() {
+        #[derive(Serialize)]
+        struct V3 {
+            name: String,
+            kind: HolidayKind,
+            translations: BTreeMap<String, String>,
+            regions: Vec<String>,
+        }
+
+        let fname = temp_file("v3");
+        let mut v3 = HashMap::new();
+        for (day, name) in [(1, "Día 1"), (2, "Día 2"), (24, "Nochebuena")] {
+            v3.insert(
+                (day, 12),
+                V3 {
+                    name: name.to_string(),
+                    kind: HolidayKind::Custom,
+                    translations: BTreeMap::new(),
+                    regions: vec!["ES-MD".to_string()],
+                },
+            );
+        }
+        {
+            let mut file = File::create(&fname).expect("create v3 file");
+            bincode::serialize_into(&mut file, &v3).expect("serialize v3 cache");
+        }
+
+        let migrated = load(&fname)
+            .expect("v3 cache should migrate")
+            .expect("migrated cache should exist");
+        assert_eq!(migrated.len(), 3);
+        let entry = migrated.get(&(24, 12)).expect("entry survives migration");
+        assert_eq!(entry.name, "Nochebuena");
+        assert_eq!(entry.regions, ["ES-MD"]);
+        assert!(!entry.half_day);
+
+        fs::remove_file(&fname).expect("remove migrated cache");
+    }
+
+    #[test]
+    fn load_migrates_caches_without_tags() {
+        #[derive(Serialize)]
+        struct V4 {
+            name: String,
+            kind: HolidayKind,
+            translations: BTreeMap<String, String>,
+            regions: Vec<String>,
+            half_day: bool,
+        }
+
+        let fname = temp_file("v4");
+        let mut v4 = HashMap::new();
+        for (day, half_day) in [(1, true), (24, true), (31, false)] {
+            v4.insert(
+                (day, 12),
+                V4 {
+                    name: format!("Day {day}"),
+                    kind: HolidayKind::Custom,
+                    translations: BTreeMap::new(),
+                    regions: Vec::new(),
+                    half_day,
+                },
+            );
+        }
+        {
+            let mut file = File::create(&fname).expect("create v4 file");
+            bincode::serialize_into(&mut file, &v4).expect("serialize v4 cache");
+        }
+
+        let migrated = load(&fname)
+            .expect("v4 cache should migrate")
+            .expect("migrated cache should exist");
+        assert_eq!(migrated.len(), 3);
+        let entry = migrated.get(&(24, 12)).expect("entry survives migration");
+        assert!(entry.half_day);
+        assert!(entry.tags.is_empty());
+        assert!(!migrated[&(31, 12)].half_day);
+
+        fs::remove_file(&fname).expect("remove migrated cache");
+    }
+
+    #[test]
+    fn get_person_filename_ends_with_the_person() {
+        assert!(
+            get_person_filename(2026, &Provider::default(), "alice")
+                .ends_with("hm-person-argentina-datos-2026-alice")
+        );
+    }
+
+    #[test]
+    fn day_off_weighs_half_days() {
+        assert_eq!(HolidayEntry::official("Navidad").day_off(), 1.0);
+        assert_eq!(HolidayEntry::school("Winter").day_off(), 0.0);
+        let half = HolidayEntry {
+            half_day: true,
+            ..HolidayEntry::custom("Nochebuena")
+        };
+        assert_eq!(half.day_off(), 0.5);
+    }
+
+    #[test]
+    fn openholidays_entry_records_regional_scope() {
+        let resp: OpenHolidayResp = serde_json::from_str(
+            r#"{"startDate":"2026-06-04","name":[{"language":"EN","text":"Corpus Christi"}],"nationwide":false,"subdivisions":[{"code":"DE-BW","shortName":"BW"},{"code":"DE-BY","shortName":"BY"}]}"#,
+        )
+        .expect("valid response");
+
+        let (_, entry) = openholidays_entry(resp);
+        assert_eq!(entry.regions, ["DE-BW", "DE-BY"]);
+        assert!(entry.applies_to("DE-BY"));
+        assert!(!entry.applies_to("DE-BE"));
+        assert!(HolidayEntry::official("Neujahr").applies_to("DE-BE"));
+    }
+
+    #[test]
+    fn build_school_holidays_expands_ranges_within_the_year() {
+        let entries: Vec<SchoolHolidayResp> = serde_json::from_str(
+            r#"[
+                {"startDate":"2025-12-22","endDate":"2026-01-07","name":[{"language":"ES","text":"Vacaciones de Navidad"},{"language":"EN","text":"Christmas holidays"}]},
+                {"startDate":"2026-03-27","endDate":"2026-04-06","name":[{"language":"EN","text":"Easter holidays"}]},
+                {"startDate":"bad","endDate":"2026-04-06","name":[]}
+            ]"#,
+        )
+        .expect("valid response");
+
+        let hm = build_school_holidays(2026, entries);
+        assert_eq!(hm.len(), 7 + 11);
+        assert!(!hm.contains_key(&(22, 12)));
+        let entry = hm.get(&(7, 1)).expect("last day of the winter break");
+        assert_eq!(entry.kind, HolidayKind::School);
+        assert_eq!(entry.name, "Christmas holidays");
+        assert_eq!(
+            entry.clone().in_language("ES").name,
+            "Vacaciones de Navidad"
+        );
+        assert!(hm.contains_key(&(27, 3)) && hm.contains_key(&(6, 4)));
+    }
+
+    #[test]
+    fn get_school_holidays_requires_a_region() {
+        let provider = Provider::from_country(Some("ES".to_string())).expect("valid country");
+        let err = get_school_holidays(2026, &provider).expect_err("region is required");
+        assert!(matches!(err, CalError::Config(_)));
+        assert!(get_school_filename(2026, "ES-MD").ends_with("hm-school-es-md-2026"));
+    }
+
+    #[test]
+    fn hidden_overrides_roundtrip_and_mark_official_days_worked() {
+        let fname = temp_file("hidden");
+        assert!(load_hidden(&fname).expect("missing overrides").is_empty());
+
+        let hidden: Hidden = [(1, 5), (24, 12)].into_iter().collect();
+        save_hidden(&fname, &hidden).expect("save overrides");
+        assert_eq!(load_hidden(&fname).expect("load overrides"), hidden);
+        fs::remove_file(&fname).expect("remove overrides");
+
+        let mut hm = HM::new();
+        hm.insert((1, 5), HolidayEntry::official("Labour Day"));
+        hm.insert((24, 12), HolidayEntry::custom("Family dinner"));
+        apply_hidden(&mut hm, &hidden);
+        assert_eq!(hm[&(1, 5)].kind, HolidayKind::Worked);
+        assert_eq!(hm[&(24, 12)].kind, HolidayKind::Custom);
+        assert!(!HolidayKind::Worked.is_day_off());
+        assert!(
+            get_hidden_filename(2026, &Provider::default())
+                .ends_with("hm-hidden-argentina-datos-2026")
+        );
+    }
+
+    #[test]
+    fn merge_annotates_observing_countries() {
+        let mut ar = HM::new();
+        ar.insert((25, 12), HolidayEntry::official("Navidad"));
+        ar.insert((9, 7), HolidayEntry::official("Día de la Independencia"));
+        let mut us = HM::new();
+        us.insert((25, 12), HolidayEntry::official("Christmas Day"));
+        let mut de = HM::new();
+        de.insert((25, 12), HolidayEntry::official("Christmas Day"));
+
+        let merged = merge(vec![
+            ("AR".to_string(), ar),
+            ("US".to_string(), us),
+            ("DE".to_string(), de),
+        ]);
+        let christmas = merged.get(&(25, 12)).expect("shared holiday");
+        assert_eq!(christmas.name, "Navidad / Christmas Day");
+        assert_eq!(christmas.countries, ["AR", "US", "DE"]);
+        assert_eq!(merged.get(&(9, 7)).map(|e| e.countries.len()), Some(1));
+    }
+
+    #[test]
+    fn merge_counts_only_official_observers_of_official_holidays() {
+        let mut ar = HM::new();
+        ar.insert((24, 12), HolidayEntry::custom("Nochebuena"));
+        ar.insert((31, 12), HolidayEntry::custom("Fin de año"));
+        let mut de = HM::new();
+        de.insert((24, 12), HolidayEntry::official("Heiligabend"));
+        de.insert((31, 12), HolidayEntry::custom("Silvester"));
+
+        let merged = merge(vec![("AR".to_string(), ar), ("DE".to_string(), de)]);
+        assert_eq!(merged[&(24, 12)].kind, HolidayKind::Official);
+        assert_eq!(merged[&(24, 12)].countries, ["DE"]);
+        assert_eq!(merged[&(31, 12)].kind, HolidayKind::Custom);
+        assert_eq!(merged[&(31, 12)].countries, ["AR", "DE"]);
+    }
+
+    #[test]
+    fn provider_list_from_args_splits_countries() {
+        let providers = Provider::list_from_args(Some("AR,de, us".to_string()), None)
+            .expect("several countries should work");
+        let codes: Vec<&str> = providers.iter().map(Provider::country_code).collect();
+        assert_eq!(codes, ["AR", "DE", "US"]);
+
+        assert!(Provider::list_from_args(Some("DE,de".to_string()), None).is_err());
+        assert!(
+            Provider::list_from_args(Some("DE,AT".to_string()), Some("DE-BY".to_string())).is_err()
+        );
+        assert_eq!(
+            Provider::list_from_args(None, None).expect("default provider"),
+            [Provider::ArgentinaDatos]
+        );
+    }
+
+    #[test]
+    fn provider_from_args_derives_country_from_region() {
+        let provider =
+            Provider::from_args(None, Some("de-by".to_string())).expect("region alone should work");
+        assert_eq!(
+            provider,
+            Provider::OpenHolidays {
+                country_code: "DE".to_string(),
+                region: Some("DE-BY".to_string()),
+            }
+        );
+        assert_eq!(provider.region(), Some("DE-BY"));
+        assert_eq!(provider.slug(), "openholidays-de");
+
+        for (country, region) in [
+            (Some("FR"), "DE-BY"),
+            (None, "AR-B"),
+            (None, "BAVARIA"),
+            (None, "DE-"),
+        ] {
+            assert!(
+                Provider::from_args(country.map(str::to_string), Some(region.to_string())).is_err(),
+                "expected error for {country:?} / {region:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn parse_language_normalizes_iso_codes() {
+        assert_eq!(parse_language(" de ").expect("valid language"), "DE");
+        for invalid in ["", "deu", "d1"] {
+            assert!(
+                matches!(parse_language(invalid), Err(CalError::Config(_))),
+                "expected error for {invalid:?}"
+            );
+        }
+    }
+
     #[test]
     fn get_filename_places_cache_under_config_directory_for_default_provider() {
         let year = 2030;
@@ -343,6 +1268,7 @@ mod tests {
     fn get_filename_includes_provider_slug_when_not_default() {
         let provider = Provider::OpenHolidays {
             country_code: "US".to_string(),
+            region: None,
         };
         let year = 2030;
         let fname = get_filename(year, &provider);
@@ -369,7 +1295,8 @@ mod tests {
         assert_eq!(
             provider,
             Provider::OpenHolidays {
-                country_code: "US".to_string()
+                country_code: "US".to_string(),
+                region: None,
             }
         );
     }
@@ -447,17 +1374,19 @@ mod tests {
 
         let open = Provider::OpenHolidays {
             country_code: "CA".to_string(),
+            region: None,
         };
         assert!(!open.is_default());
         assert_eq!(open.slug(), "openholidays-ca");
     }
 
     #[test]
+    #[allow(clippy::unnecessary_get_then_check)]
     fn build_holidays_filters_invalid_dates() {
         let entries = vec![
-            ("2024-05-01".to_string(), "Valid".to_string()),
-            ("2024-13-01".to_string(), "Invalid".to_string()),
-            ("not-a-date".to_string(), "Bad".to_string()),
+            ("2024-05-01".to_string(), HolidayEntry::official("Valid")),
+            ("2024-13-01".to_string(), HolidayEntry::official("Invalid")),
+            ("not-a-date".to_string(), HolidayEntry::official("Bad")),
         ];
 
         let hm = build_holidays(entries);
@@ -466,4 +1395,72 @@ mod tests {
         assert!(hm.get(&(1, 13)).is_some());
         assert!(hm.iter().all(|(_, entry)| entry.name != "Bad"));
     }
+
+    #[test]
+    fn build_holidays_merges_regions_sharing_a_date() {
+        let regional = |name: &str, region: &str| HolidayEntry {
+            regions: vec![region.to_string()],
+            ..HolidayEntry::official(name)
+        };
+        let entries = vec![
+            ("2026-06-04".to_string(), regional("Fronleichnam", "DE-BW")),
+            ("2026-06-04".to_string(), regional("Fronleichnam", "DE-BY")),
+            ("2026-10-03".to_string(), regional("Regional", "DE-BE")),
+            ("2026-10-03".to_string(), HolidayEntry::official("Einheit")),
+        ];
+
+        let hm = build_holidays(entries);
+        let fronleichnam = &hm[&(4, 6)];
+        assert_eq!(fronleichnam.regions, ["DE-BW", "DE-BY"]);
+        assert!(fronleichnam.applies_to("DE-BW") && fronleichnam.applies_to("DE-BY"));
+        assert!(!fronleichnam.applies_to("DE-BE"));
+        assert_eq!(hm[&(3, 10)].name, "Einheit");
+        assert!(hm[&(3, 10)].regions.is_empty());
+    }
+
+    #[test]
+    #[serial]
+    fn load_custom_moves_entries_out_of_the_provider_cache() {
+        let home = std::env::temp_dir().join(format!(
+            "cal2-home-custom-{}",
+            SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .expect("time went backwards")
+                .as_nanos()
+        ));
+        fs::create_dir_all(home.join(".config")).expect("create config directory");
+        let previous = std::env::var("HOME").ok();
+        unsafe {
+            std::env::set_var("HOME", &home);
+        }
+
+        let provider = Provider::default();
+        let mut cached = HM::new();
+        cached.insert((1, 1), HolidayEntry::official("Año Nuevo"));
+        cached.insert((24, 12), HolidayEntry::custom("Nochebuena"));
+        save(&get_filename(2044, &provider), &cached).expect("save mixed cache");
+        let mut custom_only = HM::new();
+        custom_only.insert((10, 3), HolidayEntry::custom("Vacation"));
+        save(&get_filename(2045, &provider), &custom_only).expect("save custom-only cache");
+
+        let custom = load_custom(2044, &provider).expect("migrate mixed cache");
+        assert_eq!(custom.keys().collect::<Vec<_>>(), [&(24, 12)]);
+        let fetched = load(&get_filename(2044, &provider))
+            .expect("load cache")
+            .expect("cache kept");
+        assert_eq!(fetched.keys().collect::<Vec<_>>(), [&(1, 1)]);
+        assert_eq!(load_custom(2044, &provider).expect("reload"), custom);
+
+        assert_eq!(load_custom(2045, &provider).expect("migrate"), custom_only);
+        assert!(!Path::new(&get_filename(2045, &provider)).exists());
+
+        unsafe {
+            if let Some(prev) = previous {
+                std::env::set_var("HOME", prev);
+            } else {
+                std::env::remove_var("HOME");
+            }
+        }
+        let _ = fs::remove_dir_all(&home);
+    }
 }
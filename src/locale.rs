@@ -0,0 +1,132 @@
+use crate::error::{CalError, Result};
+use chrono::{Locale, NaiveDate, Weekday};
+
+const WEEK: [Weekday; 7] = [
+    Weekday::Mon,
+    Weekday::Tue,
+    Weekday::Wed,
+    Weekday::Thu,
+    Weekday::Fri,
+    Weekday::Sat,
+    Weekday::Sun,
+];
+
+pub fn parse(name: &str) -> Result<Locale> {
+    let trimmed = name.trim();
+    let base = trimmed
+        .split('.')
+        .next()
+        .unwrap_or(trimmed)
+        .replace('-', "_");
+    base.parse().map_err(|_| {
+        CalError::Config(format!(
+            "unknown locale {trimmed:?}; use a name such as es_AR or de_DE"
+        ))
+    })
+}
+
+pub fn month_title(month: u32, year: i32, locale: Locale) -> Option<String> {
+    let date = NaiveDate::from_ymd_opt(year, month, 1)?;
+    Some(capitalize(
+        &date.format_localized("%B %Y", locale).to_string(),
+    ))
+}
+
+pub fn month_name(month: u32, locale: Locale) -> Option<String> {
+    let date = NaiveDate::from_ymd_opt(2024, month, 1)?;
+    Some(capitalize(&date.format_localized("%B", locale).to_string()))
+}
+
+pub fn weekday_name(weekday: Weekday, locale: Locale) -> String {
+    NaiveDate::from_isoywd_opt(2024, 1, weekday)
+        .map(|date| capitalize(&date.format_localized("%a", locale).to_string()))
+        .unwrap_or_default()
+}
+
+pub fn weekday_labels(locale: Locale) -> Vec<String> {
+    WEEK.iter()
+        .map(|weekday| {
+            NaiveDate::from_isoywd_opt(2024, 1, *weekday)
+                .map(|date| {
+                    let abbreviation = date.format_localized("%a", locale).to_string();
+                    capitalize(&abbreviation.chars().take(2).collect::<String>())
+                })
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+pub fn month_first(locale: Locale) -> bool {
+    let Some(sample) = NaiveDate::from_ymd_opt(2026, 12, 24) else {
+        return false;
+    };
+    let short = sample.format_localized("%x", locale).to_string();
+    match (short.find("12"), short.find("24")) {
+        (Some(month), Some(day)) => month < day,
+        _ => false,
+    }
+}
+
+pub fn html_lang(locale: Locale) -> String {
+    match locale {
+        Locale::POSIX => "en".to_string(),
+        other => other.to_string().replace('_', "-"),
+    }
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_accepts_common_spellings() {
+        assert_eq!(parse("es_AR").expect("valid locale"), Locale::es_AR);
+        assert_eq!(parse("de-DE").expect("valid locale"), Locale::de_DE);
+        assert_eq!(parse(" pt_BR.UTF-8 ").expect("valid locale"), Locale::pt_BR);
+        assert!(matches!(parse("xx_YY"), Err(CalError::Config(_))));
+    }
+
+    #[test]
+    fn month_title_is_localized_and_capitalized() {
+        assert_eq!(
+            month_title(1, 2026, Locale::es_AR).as_deref(),
+            Some("Enero 2026")
+        );
+        assert_eq!(
+            month_title(3, 2026, Locale::de_DE).as_deref(),
+            Some("März 2026")
+        );
+        assert_eq!(month_title(13, 2026, Locale::de_DE), None);
+        assert_eq!(month_name(3, Locale::de_DE).as_deref(), Some("März"));
+        assert_eq!(weekday_name(Weekday::Mon, Locale::es_AR), "Lun");
+    }
+
+    #[test]
+    fn month_first_detects_us_style_dates() {
+        assert!(month_first(Locale::en_US));
+        assert!(!month_first(Locale::es_AR));
+        assert!(!month_first(Locale::de_DE));
+        assert!(!month_first(Locale::en_GB));
+    }
+
+    #[test]
+    fn weekday_labels_start_on_monday() {
+        assert_eq!(
+            weekday_labels(Locale::de_DE),
+            ["Mo", "Di", "Mi", "Do", "Fr", "Sa", "So"]
+        );
+        assert_eq!(
+            weekday_labels(Locale::POSIX),
+            ["Mo", "Tu", "We", "Th", "Fr", "Sa", "Su"]
+        );
+        assert_eq!(weekday_labels(Locale::es_AR)[0], "Lu");
+    }
+}
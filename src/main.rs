@@ -1,10 +1,18 @@
 use clap::Parser;
 use std::{collections::HashMap, ffi::OsString, process};
 
+mod calendar;
 mod cli;
+mod config;
+mod dates;
 mod display_month;
 mod error;
 mod holidays;
+mod locale;
+mod recurring;
+mod render;
+mod team_file;
+mod template;
 
 use error::Result;
 use holidays::HolidayEntry;
@@ -0,0 +1,221 @@
+use crate::HM;
+use crate::error::{CalError, Result};
+use crate::holidays::{HolidayEntry, Provider};
+use chrono::{Datelike, Days, Months, NaiveDate};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+
+/// How a rule repeats, relative to the date it was created from.
+#[derive(ValueEnum, Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Recurrence {
+    /// Same day and month every year.
+    Yearly,
+    /// Same weekday occurrence every month, e.g. the first Friday.
+    Monthly,
+    /// Same weekday every other week.
+    Biweekly,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Rule {
+    pub start: NaiveDate,
+    pub recurrence: Recurrence,
+    pub name: String,
+}
+
+pub type Rules = Vec<Rule>;
+
+impl Rule {
+    pub fn new(start: NaiveDate, recurrence: Recurrence, name: String) -> Self {
+        Self {
+            start,
+            recurrence,
+            name,
+        }
+    }
+
+    /// Dates of `year` on which the rule applies, never before its start.
+    pub fn dates(&self, year: i32) -> Vec<NaiveDate> {
+        if year < self.start.year() {
+            return Vec::new();
+        }
+        match self.recurrence {
+            Recurrence::Yearly => {
+                NaiveDate::from_ymd_opt(year, self.start.month(), self.start.day())
+                    .into_iter()
+                    .collect()
+            }
+            Recurrence::Monthly => (1..=12)
+                .filter_map(|month| {
+                    NaiveDate::from_weekday_of_month_opt(
+                        year,
+                        month,
+                        self.start.weekday(),
+                        self.nth_weekday(),
+                    )
+                })
+                .filter(|date| *date >= self.start)
+                .collect(),
+            Recurrence::Biweekly => {
+                let mut dates = Vec::new();
+                let mut date = self.start;
+                while date.year() <= year {
+                    if date.year() == year {
+                        dates.push(date);
+                    }
+                    match date.checked_add_days(Days::new(14)) {
+                        Some(next) => date = next,
+                        None => break,
+                    }
+                }
+                dates
+            }
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        let weekday = self.start.format("%A");
+        match self.recurrence {
+            Recurrence::Yearly => format!("every year on {}", self.start.format("%d %B")),
+            Recurrence::Monthly => {
+                let nth = ["first", "second", "third", "fourth", "fifth"]
+                    [usize::from(self.nth_weekday() - 1)];
+                format!("every {nth} {weekday} of the month")
+            }
+            Recurrence::Biweekly => format!("every other {weekday}"),
+        }
+    }
+
+    fn nth_weekday(&self) -> u8 {
+        // Day 1-7 is the first occurrence, 8-14 the second, and so on.
+        (self.start.day0() / 7 + 1) as u8
+    }
+}
+
+pub fn get_rules_filename(provider: &Provider) -> String {
+    let basename = format!("hm-rules-{}", provider.slug());
+    shellexpand::tilde(&format!("~/.config/{basename}")).to_string()
+}
+
+pub fn get_person_rules_filename(provider: &Provider, person: &str) -> String {
+    let basename = format!("hm-person-rules-{}-{person}", provider.slug());
+    shellexpand::tilde(&format!("~/.config/{basename}")).to_string()
+}
+
+pub fn load_rules(fname: &str) -> Result<Rules> {
+    match fs::read(fname) {
+        Ok(bytes) => bincode::deserialize(&bytes)
+            .map_err(|_| CalError::Cache(format!("failed to deserialize rules {fname}"))),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Rules::new()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+pub fn save_rules(fname: &str, rules: &Rules) -> Result<()> {
+    let file = File::create(fname)?;
+    let mut writer = BufWriter::new(file);
+    bincode::serialize_into(&mut writer, rules)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Adds the occurrences of every rule in `year` as custom entries, leaving
+/// dates that already have an entry untouched.
+pub fn apply_rules(hm: &mut HM, rules: &[Rule], year: i32) {
+    for rule in rules {
+        for date in rule.dates(year) {
+            hm.entry((date.day(), date.month()))
+                .or_insert_with(|| HolidayEntry::custom(rule.name.clone()));
+        }
+    }
+}
+
+/// The rule's next occurrence on or after `today`, looking two years ahead.
+pub fn next_after(rule: &Rule, today: NaiveDate) -> Option<NaiveDate> {
+    let horizon = today.checked_add_months(Months::new(24))?;
+    (today.year()..=horizon.year())
+        .flat_map(|year| rule.dates(year))
+        .find(|date| *date >= today)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::holidays::HolidayKind;
+    use std::time::SystemTime;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).expect("valid date")
+    }
+
+    #[test]
+    fn yearly_rules_repeat_from_their_start_year() {
+        let rule = Rule::new(date(2026, 3, 14), Recurrence::Yearly, "Birthday".into());
+        assert!(rule.dates(2025).is_empty());
+        assert_eq!(rule.dates(2030), vec![date(2030, 3, 14)]);
+
+        let leap = Rule::new(date(2024, 2, 29), Recurrence::Yearly, "Leap".into());
+        assert!(leap.dates(2026).is_empty());
+        assert_eq!(leap.dates(2028), vec![date(2028, 2, 29)]);
+    }
+
+    #[test]
+    fn monthly_rules_keep_the_weekday_occurrence() {
+        // 2026-11-06 is the first Friday of November.
+        let rule = Rule::new(date(2026, 11, 6), Recurrence::Monthly, "Team".into());
+        assert_eq!(rule.dates(2026), vec![date(2026, 11, 6), date(2026, 12, 4)]);
+        assert_eq!(rule.dates(2027).len(), 12);
+        assert_eq!(rule.dates(2027)[0], date(2027, 1, 1));
+        assert_eq!(rule.describe(), "every first Friday of the month");
+    }
+
+    #[test]
+    fn biweekly_rules_skip_every_other_week() {
+        let rule = Rule::new(date(2026, 12, 18), Recurrence::Biweekly, "9/80".into());
+        assert_eq!(rule.dates(2026), vec![date(2026, 12, 18)]);
+        let next = rule.dates(2027);
+        assert_eq!(next[0], date(2027, 1, 1));
+        assert_eq!(next[1], date(2027, 1, 15));
+        assert_eq!(next.len(), 27);
+        assert_eq!(next[26], date(2027, 12, 31));
+        assert_eq!(rule.describe(), "every other Friday");
+    }
+
+    #[test]
+    fn apply_rules_does_not_override_existing_entries() {
+        let mut hm = HM::new();
+        hm.insert((25, 12), HolidayEntry::official("Navidad"));
+        let rules = vec![
+            Rule::new(date(2026, 12, 25), Recurrence::Yearly, "Mine".into()),
+            Rule::new(date(2026, 3, 14), Recurrence::Yearly, "Birthday".into()),
+        ];
+        apply_rules(&mut hm, &rules, 2027);
+        assert_eq!(hm[&(25, 12)].name, "Navidad");
+        assert_eq!(hm[&(14, 3)].kind, HolidayKind::Custom);
+        assert_eq!(hm[&(14, 3)].name, "Birthday");
+    }
+
+    #[test]
+    fn rules_roundtrip_through_disk() {
+        let nanos = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .expect("clock after epoch")
+            .as_nanos();
+        let fname = std::env::temp_dir()
+            .join(format!("cal2-rules-{nanos}"))
+            .to_string_lossy()
+            .to_string();
+        assert!(load_rules(&fname).expect("missing rules").is_empty());
+        let rules = vec![Rule::new(
+            date(2026, 11, 6),
+            Recurrence::Monthly,
+            "Team".into(),
+        )];
+        save_rules(&fname, &rules).expect("save rules");
+        assert_eq!(load_rules(&fname).expect("load rules"), rules);
+        fs::remove_file(&fname).expect("cleanup");
+        assert!(get_rules_filename(&Provider::default()).ends_with("hm-rules-argentina-datos"));
+    }
+}
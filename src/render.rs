@@ -0,0 +1,72 @@
+pub mod html;
+pub mod json;
+pub mod page;
+pub mod pdf;
+pub mod svg;
+
+use crate::display_month::DisplayMonth;
+
+pub fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+pub fn number(value: f64) -> String {
+    let formatted = format!("{value:.2}");
+    formatted
+        .trim_end_matches('0')
+        .trim_end_matches('.')
+        .to_string()
+}
+
+pub fn title(calendars: &[DisplayMonth]) -> String {
+    match (calendars.first(), calendars.last()) {
+        (Some(first), Some(last)) if calendars.len() > 1 => {
+            format!("{} – {}", first.month_name, last.month_name)
+        }
+        (Some(first), _) => first.month_name.clone(),
+        _ => "Calendar".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::HM;
+
+    #[test]
+    fn escape_xml_replaces_markup_characters() {
+        assert_eq!(
+            escape_xml(r#"<b>"Tom" & 'Jerry'</b>"#),
+            "&lt;b&gt;&quot;Tom&quot; &amp; &#39;Jerry&#39;&lt;/b&gt;"
+        );
+    }
+
+    #[test]
+    fn number_trims_trailing_zeros() {
+        assert_eq!(number(595.0), "595");
+        assert_eq!(number(12.5), "12.5");
+        assert_eq!(number(1.0 / 3.0), "0.33");
+    }
+
+    #[test]
+    fn title_spans_first_and_last_month() {
+        let hm = HM::new();
+        let months = vec![
+            DisplayMonth::new(1, 2026, &hm).expect("valid month"),
+            DisplayMonth::new(3, 2026, &hm).expect("valid month"),
+        ];
+        assert_eq!(title(&months), "January 2026 – March 2026");
+        assert_eq!(title(&months[..1]), "January 2026");
+    }
+}
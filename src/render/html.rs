@@ -0,0 +1,192 @@
+use super::{escape_xml, title};
+use crate::display_month::DisplayMonth;
+use crate::holidays::HolidayKind;
+use crate::locale;
+use chrono::Datelike;
+use std::fmt::Write;
+
+const STYLE: &str = "\
+body { font-family: sans-serif; margin: 2em; color: #222; }
+.calendars { display: flex; flex-wrap: wrap; gap: 2em; }
+table.month { border-collapse: collapse; }
+table.month caption { font-weight: bold; padding-bottom: 0.5em; }
+table.month th, table.month td { width: 2.2em; height: 2em; text-align: center; }
+td.weekend { color: #2e7d32; }
+td.holiday { background: #ffcdd2; color: #b71c1c; }
+td.custom { background: #bbdefb; color: #0d47a1; }
+td.school { background: #fff9c4; color: #795548; }
+td.leave { background: #b2ebf2; color: #006064; }
+td.shared { background: #b71c1c; color: #fff; }
+td.half { background-image: linear-gradient(135deg, transparent 50%, #fff 50%); }
+td.today { outline: 2px solid #222; font-weight: bold; }
+td[title] { cursor: help; }
+";
+
+pub fn render(calendars: &[DisplayMonth]) -> String {
+    let title = escape_xml(&title(calendars));
+    let mut page = String::new();
+    let lang = calendars
+        .first()
+        .map(|calendar| locale::html_lang(calendar.locale))
+        .unwrap_or_else(|| "en".to_string());
+    let _ = writeln!(page, "<!DOCTYPE html>\n<html lang=\"{lang}\">");
+    page.push_str("<head>\n<meta charset=\"utf-8\">\n");
+    let _ = writeln!(page, "<title>{title}</title>");
+    let _ = writeln!(page, "<style>\n{STYLE}</style>");
+    page.push_str("</head>\n<body>\n");
+    let _ = writeln!(page, "<h1>{title}</h1>");
+    page.push_str("<div class=\"calendars\">\n");
+    for calendar in calendars {
+        render_month(&mut page, calendar);
+    }
+    page.push_str("</div>\n</body>\n</html>\n");
+    page
+}
+
+fn render_month(page: &mut String, calendar: &DisplayMonth) {
+    page.push_str("<table class=\"month\">\n");
+    let _ = writeln!(
+        page,
+        "<caption>{}</caption>",
+        escape_xml(&calendar.month_name)
+    );
+    page.push_str("<thead><tr>");
+    for label in &calendar.weekdays {
+        let _ = write!(page, "<th>{label}</th>");
+    }
+    page.push_str("</tr></thead>\n<tbody>\n");
+    for week in calendar.weeks() {
+        page.push_str("<tr>");
+        for day in &week {
+            let Some(day) = day else {
+                page.push_str("<td></td>");
+                continue;
+            };
+            let mut classes = Vec::new();
+            if day.weekend {
+                classes.push("weekend".to_string());
+            }
+            if let Some(holiday) = day.holiday {
+                let kind = match holiday.kind {
+                    HolidayKind::Official => "holiday",
+                    HolidayKind::Custom => "custom",
+                    HolidayKind::School => "school",
+                    HolidayKind::Leave => "leave",
+                    HolidayKind::Worked => "worked",
+                };
+                classes.push(kind.to_string());
+                if holiday.half_day {
+                    classes.push("half".to_string());
+                }
+                classes.extend(holiday.tags.iter().map(|tag| format!("tag-{tag}")));
+            }
+            if day.shared {
+                classes.push("shared".to_string());
+            }
+            if day.today {
+                classes.push("today".to_string());
+            }
+            page.push_str("<td");
+            if !classes.is_empty() {
+                let _ = write!(page, " class=\"{}\"", escape_xml(&classes.join(" ")));
+            }
+            if let Some(color) = day.tag_color {
+                let _ = write!(page, " style=\"border-bottom: 3px solid {}\"", color.name());
+            }
+            if let Some(holiday) = day.holiday {
+                let tooltip = if holiday.countries.is_empty() {
+                    holiday.name.clone()
+                } else {
+                    format!("{} ({})", holiday.name, holiday.countries.join(", "))
+                };
+                let _ = write!(page, " title=\"{}\"", escape_xml(&tooltip));
+            }
+            let _ = write!(page, ">{}</td>", day.date.day());
+        }
+        for _ in week.len()..7 {
+            page.push_str("<td></td>");
+        }
+        page.push_str("</tr>\n");
+    }
+    page.push_str("</tbody>\n</table>\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::HM;
+    use crate::config::TagColor;
+    use crate::holidays::HolidayEntry;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn render_produces_standalone_page() {
+        let hm = HM::new();
+        let months = vec![DisplayMonth::new(1, 2026, &hm).expect("valid month")];
+
+        let page = render(&months);
+        assert!(page.starts_with("<!DOCTYPE html>"));
+        assert!(page.contains("<style>"));
+        assert!(page.contains("<caption>January 2026</caption>"));
+        assert!(page.trim_end().ends_with("</html>"));
+    }
+
+    #[test]
+    fn render_marks_days_with_css_classes_and_tooltips() {
+        let mut hm = HM::new();
+        hm.insert((1, 1), HolidayEntry::official("New Year's Day"));
+        hm.insert((13, 1), HolidayEntry::custom("Team <offsite>"));
+        hm.insert((7, 1), HolidayEntry::school("Christmas holidays"));
+        let today = NaiveDate::from_ymd_opt(2026, 1, 14).expect("valid date");
+        let months = vec![
+            DisplayMonth::new(1, 2026, &hm)
+                .expect("valid month")
+                .with_today(today),
+        ];
+
+        let page = render(&months);
+        assert!(page.contains("<td class=\"holiday\" title=\"New Year&#39;s Day\">1</td>"));
+        assert!(page.contains("<td class=\"custom\" title=\"Team &lt;offsite&gt;\">13</td>"));
+        assert!(page.contains("<td class=\"school\" title=\"Christmas holidays\">7</td>"));
+        assert!(page.contains("<td class=\"today\">14</td>"));
+        assert!(page.contains("<td class=\"weekend\">3</td>"));
+    }
+
+    #[test]
+    fn render_marks_half_days() {
+        let mut hm = HM::new();
+        hm.insert(
+            (24, 12),
+            HolidayEntry {
+                half_day: true,
+                ..HolidayEntry::custom("Christmas Eve")
+            },
+        );
+        let months = vec![DisplayMonth::new(12, 2026, &hm).expect("valid month")];
+
+        let page = render(&months);
+        assert!(page.contains("<td class=\"custom half\" title=\"Christmas Eve\">24</td>"));
+    }
+
+    #[test]
+    fn render_marks_tags_with_classes_and_colours() {
+        let mut hm = HM::new();
+        hm.insert(
+            (3, 12),
+            HolidayEntry {
+                tags: vec!["oncall".to_string()],
+                ..HolidayEntry::custom("Rotation")
+            },
+        );
+        let months = vec![
+            DisplayMonth::new(12, 2026, &hm)
+                .expect("valid month")
+                .with_tag_colors([("oncall".to_string(), TagColor::Magenta)].into()),
+        ];
+
+        let page = render(&months);
+        assert!(page.contains(
+            "<td class=\"custom tag-oncall\" style=\"border-bottom: 3px solid magenta\" title=\"Rotation\">3</td>"
+        ));
+    }
+}
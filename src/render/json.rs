@@ -0,0 +1,107 @@
+use crate::display_month::{Day, DisplayMonth};
+use crate::error::Result;
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct MonthRecord<'a> {
+    name: &'a str,
+    year: i32,
+    month: u32,
+    weeks: Vec<Vec<Option<DayRecord<'a>>>>,
+}
+
+#[derive(Serialize)]
+struct DayRecord<'a> {
+    date: String,
+    weekday: String,
+    weekend: bool,
+    today: bool,
+    shared: bool,
+    holidays: Vec<HolidayRecord<'a>>,
+}
+
+#[derive(Serialize)]
+struct HolidayRecord<'a> {
+    name: &'a str,
+    kind: &'static str,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    half_day: bool,
+    #[serde(skip_serializing_if = "<[String]>::is_empty")]
+    tags: &'a [String],
+    #[serde(skip_serializing_if = "<[String]>::is_empty")]
+    countries: &'a [String],
+}
+
+impl<'a> From<Day<'a>> for DayRecord<'a> {
+    fn from(day: Day<'a>) -> Self {
+        Self {
+            date: day.date.format("%Y-%m-%d").to_string(),
+            weekday: day.date.format("%a").to_string(),
+            weekend: day.weekend,
+            today: day.today,
+            shared: day.shared,
+            holidays: day
+                .holiday
+                .into_iter()
+                .map(|entry| HolidayRecord {
+                    name: &entry.name,
+                    kind: entry.kind.label(),
+                    half_day: entry.half_day,
+                    tags: &entry.tags,
+                    countries: &entry.countries,
+                })
+                .collect(),
+        }
+    }
+}
+
+pub fn render(calendars: &[DisplayMonth]) -> Result<String> {
+    let months: Vec<MonthRecord> = calendars
+        .iter()
+        .map(|calendar| MonthRecord {
+            name: &calendar.month_name,
+            year: calendar.year,
+            month: calendar.month,
+            weeks: calendar
+                .weeks()
+                .into_iter()
+                .map(|week| {
+                    week.into_iter()
+                        .map(|day| day.map(DayRecord::from))
+                        .collect()
+                })
+                .collect(),
+        })
+        .collect();
+    Ok(serde_json::to_string_pretty(&months)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::HM;
+    use crate::holidays::HolidayEntry;
+
+    #[test]
+    fn render_describes_weeks_and_days() {
+        let mut hm = HM::new();
+        hm.insert((24, 12), HolidayEntry::custom("Family dinner"));
+        let months = vec![DisplayMonth::new(12, 2026, &hm).expect("valid month")];
+
+        let value: serde_json::Value =
+            serde_json::from_str(&render(&months).expect("render succeeds")).expect("valid json");
+        let month = &value[0];
+        assert_eq!(month["name"], "December 2026");
+        assert_eq!(month["year"], 2026);
+        assert_eq!(month["month"], 12);
+        let weeks = month["weeks"].as_array().expect("weeks array");
+        assert_eq!(weeks.len(), 5);
+        assert_eq!(weeks[0][1]["date"], "2026-12-01");
+        assert_eq!(weeks[0][1]["weekday"], "Tue");
+        assert_eq!(weeks[0][5]["weekend"], true);
+        assert_eq!(weeks[3][3]["date"], "2026-12-24");
+        assert_eq!(weeks[3][3]["holidays"][0]["name"], "Family dinner");
+        assert_eq!(weeks[3][3]["holidays"][0]["kind"], "custom");
+        assert_eq!(weeks[0][2]["holidays"].as_array().map(Vec::len), Some(0));
+    }
+}
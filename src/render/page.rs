@@ -0,0 +1,312 @@
+use super::title;
+use crate::display_month::DisplayMonth;
+use crate::holidays::HolidayKind;
+use chrono::Datelike;
+
+const MARGIN: f64 = 36.0;
+const GAP: f64 = 14.0;
+const TITLE_SIZE: f64 = 18.0;
+const LINE_HEIGHT: f64 = 1.15;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Color(pub u8, pub u8, pub u8);
+
+const INK: Color = Color(0x22, 0x22, 0x22);
+const GRID: Color = Color(0xbd, 0xbd, 0xbd);
+const WEEKEND: Color = Color(0xe8, 0xf5, 0xe9);
+const HOLIDAY: Color = Color(0xff, 0xcd, 0xd2);
+const CUSTOM: Color = Color(0xbb, 0xde, 0xfb);
+const SCHOOL: Color = Color(0xff, 0xf9, 0xc4);
+const LEAVE: Color = Color(0xb2, 0xeb, 0xf2);
+const SHARED: Color = Color(0xe5, 0x73, 0x73);
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Shape {
+    Rect {
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+        fill: Option<Color>,
+        stroke: Color,
+    },
+    Text {
+        x: f64,
+        y: f64,
+        size: f64,
+        bold: bool,
+        color: Color,
+        text: String,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Page {
+    pub width: f64,
+    pub height: f64,
+    pub shapes: Vec<Shape>,
+}
+
+pub fn layout(calendars: &[DisplayMonth], (width, height): (f64, f64)) -> Page {
+    let mut shapes = Vec::new();
+    centered_text(
+        &mut shapes,
+        width / 2.0,
+        MARGIN + TITLE_SIZE,
+        TITLE_SIZE,
+        &title(calendars),
+    );
+
+    let columns = match calendars.len() {
+        0..=3 => 1,
+        4..=6 => 2,
+        _ => 3,
+    };
+    let rows = calendars.len().div_ceil(columns).max(1);
+    let top = MARGIN + TITLE_SIZE + GAP;
+    let box_width = (width - 2.0 * MARGIN - GAP * (columns - 1) as f64) / columns as f64;
+    let box_height = (height - top - MARGIN - GAP * (rows - 1) as f64) / rows as f64;
+    for (index, calendar) in calendars.iter().enumerate() {
+        let x = MARGIN + (index % columns) as f64 * (box_width + GAP);
+        let y = top + (index / columns) as f64 * (box_height + GAP);
+        layout_month(&mut shapes, calendar, x, y, box_width, box_height);
+    }
+
+    Page {
+        width,
+        height,
+        shapes,
+    }
+}
+
+fn layout_month(
+    shapes: &mut Vec<Shape>,
+    calendar: &DisplayMonth,
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+) {
+    let caption_size = (height * 0.06).clamp(9.0, 16.0);
+    centered_text(
+        shapes,
+        x + width / 2.0,
+        y + caption_size,
+        caption_size,
+        &calendar.month_name,
+    );
+
+    let cell_width = width / 7.0;
+    let header_size = caption_size * 0.7;
+    let header_baseline = y + caption_size * 1.5 + header_size;
+    for (index, label) in calendar.weekdays.iter().enumerate() {
+        centered_text(
+            shapes,
+            x + cell_width * (index as f64 + 0.5),
+            header_baseline,
+            header_size,
+            label,
+        );
+    }
+
+    let grid_top = header_baseline + header_size * 0.6;
+    let cell_height = (y + height - grid_top) / 6.0;
+    let number_size = (cell_height * 0.3).min(cell_width * 0.3).clamp(4.0, 11.0);
+    let name_size = (number_size * 0.6).max(3.0);
+    let max_lines =
+        ((cell_height - number_size - 3.0) / (name_size * LINE_HEIGHT)).max(0.0) as usize;
+
+    for (row, week) in calendar.weeks().iter().enumerate() {
+        for (column, day) in week.iter().enumerate() {
+            let Some(day) = day else {
+                continue;
+            };
+            let cell_x = x + column as f64 * cell_width;
+            let cell_y = grid_top + row as f64 * cell_height;
+            let fill = match day.holiday.map(|h| h.kind) {
+                Some(_) if day.shared => Some(SHARED),
+                Some(HolidayKind::Official) => Some(HOLIDAY),
+                Some(HolidayKind::Custom) => Some(CUSTOM),
+                Some(HolidayKind::School) => Some(SCHOOL),
+                Some(HolidayKind::Leave) => Some(LEAVE),
+                Some(HolidayKind::Worked) => None,
+                None if day.weekend => Some(WEEKEND),
+                None => None,
+            };
+            shapes.push(Shape::Rect {
+                x: cell_x,
+                y: cell_y,
+                width: cell_width,
+                height: cell_height,
+                fill,
+                stroke: if day.today { INK } else { GRID },
+            });
+            shapes.push(Shape::Text {
+                x: cell_x + 2.0,
+                y: cell_y + number_size + 1.0,
+                size: number_size,
+                bold: day.holiday.is_some(),
+                color: INK,
+                text: day.date.day().to_string(),
+            });
+
+            let Some(holiday) = day.holiday else {
+                continue;
+            };
+            let lines = wrap(&holiday.name, name_size, cell_width - 3.0, max_lines);
+            for (index, line) in lines.into_iter().enumerate() {
+                shapes.push(Shape::Text {
+                    x: cell_x + 1.5,
+                    y: cell_y + number_size + 2.0 + (index + 1) as f64 * name_size * LINE_HEIGHT,
+                    size: name_size,
+                    bold: false,
+                    color: INK,
+                    text: line,
+                });
+            }
+        }
+    }
+}
+
+fn centered_text(shapes: &mut Vec<Shape>, center: f64, baseline: f64, size: f64, text: &str) {
+    shapes.push(Shape::Text {
+        x: center - text_width(text, size) / 2.0,
+        y: baseline,
+        size,
+        bold: true,
+        color: INK,
+        text: text.to_string(),
+    });
+}
+
+// Advance widths of Helvetica for printable ASCII, in 1/1000 em.
+const HELVETICA_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278, 556, 556, 556,
+    556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556, 1015, 667, 667, 722, 722, 667,
+    611, 778, 722, 278, 500, 667, 556, 833, 722, 778, 667, 778, 722, 667, 611, 722, 667, 944, 667,
+    667, 611, 278, 278, 278, 469, 556, 333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500,
+    222, 833, 556, 556, 556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584,
+];
+
+pub fn text_width(text: &str, size: f64) -> f64 {
+    let units: u32 = text
+        .chars()
+        .map(|c| match c {
+            ' '..='~' => u32::from(HELVETICA_WIDTHS[c as usize - 32]),
+            _ => 556,
+        })
+        .sum();
+    f64::from(units) * size / 1000.0
+}
+
+fn wrap(text: &str, size: f64, max_width: f64, max_lines: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    for word in text.split_whitespace() {
+        let candidate = if current.is_empty() {
+            word.to_string()
+        } else {
+            format!("{current} {word}")
+        };
+        if text_width(&candidate, size) <= max_width {
+            current = candidate;
+            continue;
+        }
+        if !current.is_empty() {
+            lines.push(std::mem::take(&mut current));
+        }
+        current = fit(word, size, max_width);
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+
+    if lines.len() > max_lines {
+        lines.truncate(max_lines);
+        if let Some(last) = lines.last_mut() {
+            let kept = fit(last, size, max_width - text_width("...", size));
+            *last = format!("{kept}...");
+        }
+    }
+    lines
+}
+
+fn fit(text: &str, size: f64, max_width: f64) -> String {
+    let mut fitted = String::new();
+    for c in text.chars() {
+        fitted.push(c);
+        if text_width(&fitted, size) > max_width {
+            fitted.pop();
+            break;
+        }
+    }
+    fitted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::HM;
+    use crate::holidays::HolidayEntry;
+
+    #[test]
+    fn text_width_uses_helvetica_metrics() {
+        assert_eq!(text_width("", 10.0), 0.0);
+        assert!((text_width("ii", 10.0) - 4.44).abs() < 1e-9);
+        assert!(text_width("WW", 10.0) > text_width("ii", 10.0));
+    }
+
+    #[test]
+    fn wrap_splits_words_and_truncates_overflow() {
+        let lines = wrap("Día de la Independencia", 10.0, 60.0, 5);
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0], "Día de la");
+        assert!(lines[1].starts_with("Independ"));
+
+        let lines = wrap("one two three four", 10.0, 30.0, 2);
+        assert_eq!(lines.len(), 2);
+        assert!(lines[1].ends_with("..."));
+        assert!(lines.iter().all(|line| text_width(line, 10.0) <= 30.0));
+
+        assert!(wrap("anything", 10.0, 30.0, 0).is_empty());
+    }
+
+    #[test]
+    fn layout_places_months_and_holiday_names_on_page() {
+        let mut hm = HM::new();
+        hm.insert((25, 12), HolidayEntry::official("Christmas"));
+        let months: Vec<_> = (1..=12)
+            .map(|month| DisplayMonth::new(month, 2026, &hm).expect("valid month"))
+            .collect();
+
+        let page = layout(&months, (595.0, 842.0));
+        let texts: Vec<&str> = page
+            .shapes
+            .iter()
+            .filter_map(|shape| match shape {
+                Shape::Text { text, .. } => Some(text.as_str()),
+                Shape::Rect { .. } => None,
+            })
+            .collect();
+        assert!(texts.contains(&"January 2026 – December 2026"));
+        assert!(texts.contains(&"December 2026"));
+        assert!(texts.contains(&"Christmas"));
+        assert!(page.shapes.iter().any(|shape| matches!(
+            shape,
+            Shape::Rect {
+                fill: Some(HOLIDAY),
+                ..
+            }
+        )));
+        assert!(page.shapes.iter().all(|shape| match shape {
+            Shape::Rect {
+                x,
+                y,
+                width,
+                height,
+                ..
+            } => *x >= 0.0 && *y >= 0.0 && x + width <= 595.0 && y + height <= 842.0,
+            Shape::Text { .. } => true,
+        }));
+    }
+}
@@ -0,0 +1,176 @@
+use super::number;
+use super::page::{Color, Page, Shape};
+use std::fmt::Write;
+
+pub fn render(page: &Page) -> String {
+    let mut content = String::new();
+    for shape in &page.shapes {
+        match shape {
+            Shape::Rect {
+                x,
+                y,
+                width,
+                height,
+                fill,
+                stroke,
+            } => {
+                let bottom = page.height - y - height;
+                let rect = format!(
+                    "{} {} {} {} re",
+                    number(*x),
+                    number(bottom),
+                    number(*width),
+                    number(*height)
+                );
+                if let Some(fill) = fill {
+                    let _ = writeln!(content, "{} rg {rect} f", rgb(*fill));
+                }
+                let _ = writeln!(content, "{} RG 0.5 w {rect} S", rgb(*stroke));
+            }
+            Shape::Text {
+                x,
+                y,
+                size,
+                bold,
+                color,
+                text,
+            } => {
+                let font = if *bold { "F2" } else { "F1" };
+                let _ = writeln!(
+                    content,
+                    "BT /{font} {} Tf {} rg {} {} Td ({}) Tj ET",
+                    number(*size),
+                    rgb(*color),
+                    number(*x),
+                    number(page.height - y),
+                    encode_text(text),
+                );
+            }
+        }
+    }
+
+    let objects = [
+        "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+        "<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_string(),
+        format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] /Resources << /Font << /F1 4 0 R /F2 5 0 R >> >> /Contents 6 0 R >>",
+            number(page.width),
+            number(page.height)
+        ),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>"
+            .to_string(),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding /WinAnsiEncoding >>"
+            .to_string(),
+        format!(
+            "<< /Length {} >>\nstream\n{content}endstream",
+            content.len()
+        ),
+    ];
+
+    let mut pdf = String::from("%PDF-1.4\n");
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (index, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        let _ = write!(pdf, "{} 0 obj\n{object}\nendobj\n", index + 1);
+    }
+    let xref = pdf.len();
+    let _ = write!(pdf, "xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+    for offset in offsets {
+        let _ = writeln!(pdf, "{offset:010} 00000 n ");
+    }
+    let _ = write!(
+        pdf,
+        "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{xref}\n%%EOF\n",
+        objects.len() + 1
+    );
+    pdf
+}
+
+fn rgb(Color(r, g, b): Color) -> String {
+    format!(
+        "{} {} {}",
+        number(f64::from(r) / 255.0),
+        number(f64::from(g) / 255.0),
+        number(f64::from(b) / 255.0)
+    )
+}
+
+// Encodes text as a PDF literal string in WinAnsiEncoding, keeping the output ASCII-only.
+fn encode_text(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    for c in text.chars() {
+        let byte = match c {
+            '\\' | '(' | ')' => {
+                encoded.push('\\');
+                encoded.push(c);
+                continue;
+            }
+            ' '..='~' => {
+                encoded.push(c);
+                continue;
+            }
+            '\u{a0}'..='\u{ff}' => c as u32 as u8,
+            '€' => 0x80,
+            '…' => 0x85,
+            '‘' => 0x91,
+            '’' => 0x92,
+            '“' => 0x93,
+            '”' => 0x94,
+            '–' => 0x96,
+            '—' => 0x97,
+            _ => b'?',
+        };
+        if byte.is_ascii() {
+            encoded.push(byte as char);
+        } else {
+            let _ = write!(encoded, "\\{byte:03o}");
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_text_escapes_delimiters_and_latin_characters() {
+        assert_eq!(encode_text("Día (1) \\"), "D\\355a \\(1\\) \\\\");
+        assert_eq!(encode_text("Jan – Mar"), "Jan \\226 Mar");
+        assert_eq!(encode_text("日"), "?");
+    }
+
+    #[test]
+    fn render_writes_valid_cross_reference_table() {
+        let page = Page {
+            width: 595.0,
+            height: 842.0,
+            shapes: vec![Shape::Text {
+                x: 10.0,
+                y: 20.0,
+                size: 12.0,
+                bold: false,
+                color: Color(0, 0, 0),
+                text: "Año Nuevo".to_string(),
+            }],
+        };
+
+        let pdf = render(&page);
+        assert!(pdf.is_ascii());
+        assert!(pdf.starts_with("%PDF-1.4\n"));
+        assert!(pdf.contains("/MediaBox [0 0 595 842]"));
+        assert!(pdf.contains("BT /F1 12 Tf 0 0 0 rg 10 822 Td (A\\361o Nuevo) Tj ET"));
+
+        let startxref = pdf
+            .rsplit("startxref\n")
+            .next()
+            .and_then(|tail| tail.lines().next())
+            .and_then(|offset| offset.parse::<usize>().ok())
+            .expect("startxref offset");
+        assert!(pdf[startxref..].starts_with("xref\n0 7\n"));
+        for (index, line) in pdf[startxref..].lines().skip(3).take(6).enumerate() {
+            let offset: usize = line[..10].parse().expect("object offset");
+            assert!(pdf[offset..].starts_with(&format!("{} 0 obj", index + 1)));
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use super::page::{Color, Page, Shape};
+use super::{escape_xml, number};
+use std::fmt::Write;
+
+pub fn render(page: &Page) -> String {
+    let mut svg = String::new();
+    svg.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        svg,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}pt\" height=\"{h}pt\" viewBox=\"0 0 {w} {h}\" font-family=\"Helvetica, Arial, sans-serif\">",
+        w = number(page.width),
+        h = number(page.height),
+    );
+    let _ = writeln!(
+        svg,
+        "<rect width=\"{}\" height=\"{}\" fill=\"#ffffff\"/>",
+        number(page.width),
+        number(page.height)
+    );
+    for shape in &page.shapes {
+        match shape {
+            Shape::Rect {
+                x,
+                y,
+                width,
+                height,
+                fill,
+                stroke,
+            } => {
+                let fill = fill.map(hex).unwrap_or_else(|| "none".to_string());
+                let _ = writeln!(
+                    svg,
+                    "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{fill}\" stroke=\"{}\" stroke-width=\"0.5\"/>",
+                    number(*x),
+                    number(*y),
+                    number(*width),
+                    number(*height),
+                    hex(*stroke),
+                );
+            }
+            Shape::Text {
+                x,
+                y,
+                size,
+                bold,
+                color,
+                text,
+            } => {
+                let weight = if *bold { " font-weight=\"bold\"" } else { "" };
+                let _ = writeln!(
+                    svg,
+                    "<text x=\"{}\" y=\"{}\" font-size=\"{}\"{weight} fill=\"{}\">{}</text>",
+                    number(*x),
+                    number(*y),
+                    number(*size),
+                    hex(*color),
+                    escape_xml(text),
+                );
+            }
+        }
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn hex(Color(r, g, b): Color) -> String {
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_emits_shapes_as_svg_elements() {
+        let page = Page {
+            width: 100.0,
+            height: 50.0,
+            shapes: vec![
+                Shape::Rect {
+                    x: 1.0,
+                    y: 2.0,
+                    width: 10.0,
+                    height: 5.0,
+                    fill: Some(Color(0xff, 0, 0)),
+                    stroke: Color(0, 0, 0),
+                },
+                Shape::Text {
+                    x: 3.0,
+                    y: 4.5,
+                    size: 8.0,
+                    bold: true,
+                    color: Color(0x22, 0x22, 0x22),
+                    text: "Fish & Chips".to_string(),
+                },
+            ],
+        };
+
+        let svg = render(&page);
+        assert!(svg.contains("viewBox=\"0 0 100 50\""));
+        assert!(svg.contains(
+            "<rect x=\"1\" y=\"2\" width=\"10\" height=\"5\" fill=\"#ff0000\" stroke=\"#000000\" stroke-width=\"0.5\"/>"
+        ));
+        assert!(svg.contains(
+            "<text x=\"3\" y=\"4.5\" font-size=\"8\" font-weight=\"bold\" fill=\"#222222\">Fish &amp; Chips</text>"
+        ));
+        assert!(svg.trim_end().ends_with("</svg>"));
+    }
+}
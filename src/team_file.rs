@@ -0,0 +1,356 @@
+use crate::HM;
+use crate::error::{CalError, Result};
+use crate::holidays::{HolidayEntry, HolidayKind};
+use chrono::{Datelike, NaiveDate};
+use std::fs;
+use std::io;
+use std::path::Path;
+use toml_edit::{Array, DocumentMut, InlineTable, Item, Table, Value};
+
+// Shared days live in `[days]`, personal ones in `[people.<name>]`, keyed by
+// ISO date so every entry is a single line:
+//
+//     [days]
+//     2026-12-24 = "Christmas Eve"
+//     2026-12-31 = { name = "New Year's Eve", half_day = true }
+
+const DAYS: &str = "days";
+const PEOPLE: &str = "people";
+
+pub fn load(path: &Path, year: i32, person: Option<&str>) -> Result<HM> {
+    let doc = read(path)?;
+    let mut hm = HM::new();
+    let Some(section) = section(&doc, person) else {
+        return Ok(hm);
+    };
+    for (key, item) in section.iter() {
+        let date = parse_key(path, key)?;
+        if date.year() == year {
+            hm.insert((date.day(), date.month()), parse_entry(path, key, item)?);
+        }
+    }
+    Ok(hm)
+}
+
+pub fn people(path: &Path) -> Result<Vec<String>> {
+    let doc = read(path)?;
+    Ok(doc
+        .get(PEOPLE)
+        .and_then(Item::as_table_like)
+        .map(|people| people.iter().map(|(name, _)| name.to_string()).collect())
+        .unwrap_or_default())
+}
+
+/// Makes the entries of `year` in the file match `hm`, leaving other years,
+/// untouched entries, comments and layout as they are.
+pub fn save(path: &Path, year: i32, person: Option<&str>, hm: &HM) -> Result<()> {
+    let mut doc = read(path)?;
+    let section = section_mut(&mut doc, person);
+    let was_sorted = section
+        .iter()
+        .map(|(key, _)| key)
+        .is_sorted_by(|a, b| a <= b);
+
+    section.retain(|key, _| {
+        NaiveDate::parse_from_str(key, "%Y-%m-%d").map_or(true, |date| {
+            date.year() != year || hm.contains_key(&(date.day(), date.month()))
+        })
+    });
+
+    let mut added = false;
+    let mut keys: Vec<_> = hm.keys().collect();
+    keys.sort_by_key(|(day, month)| (*month, *day));
+    for (day, month) in keys {
+        let entry = &hm[&(*day, *month)];
+        let key = format!("{year}-{month:02}-{day:02}");
+        let mut value = to_value(entry);
+        match section.get_mut(&key) {
+            Some(item) => {
+                if parse_entry(path, &key, item).ok().as_ref() == Some(entry) {
+                    continue;
+                }
+                if let Some(old) = item.as_value() {
+                    *value.decor_mut() = old.decor().clone();
+                }
+                *item = Item::Value(value);
+            }
+            None => {
+                section.insert(&key, Item::Value(value));
+                added = true;
+            }
+        }
+    }
+    if added && was_sorted {
+        section.sort_values();
+    }
+
+    fs::write(path, doc.to_string())?;
+    Ok(())
+}
+
+fn read(path: &Path) -> Result<DocumentMut> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(err) => return Err(err.into()),
+    };
+    let doc: DocumentMut = contents
+        .parse()
+        .map_err(|err| CalError::Config(format!("{}: {err}", path.display())))?;
+    if let Some(people) = doc.get(PEOPLE).and_then(Item::as_table_like) {
+        for (person, _) in people.iter() {
+            check_word(path, "person", person)?;
+        }
+    }
+    Ok(doc)
+}
+
+/// Person names and tags are matched against `--person` and `--tag`, which
+/// are lowercased, so the file has to spell them the same way.
+fn check_word(path: &Path, what: &str, word: &str) -> Result<()> {
+    let valid = !word.is_empty()
+        && word.to_lowercase() == word
+        && word
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '-' || c == '_');
+    if valid {
+        return Ok(());
+    }
+    Err(CalError::Config(format!(
+        "{}: invalid {what} {word:?}; use lowercase letters, digits, '-' and '_'",
+        path.display()
+    )))
+}
+
+fn section<'a>(doc: &'a DocumentMut, person: Option<&str>) -> Option<&'a Table> {
+    match person {
+        Some(person) => doc.get(PEOPLE)?.get(person)?.as_table(),
+        None => doc.get(DAYS)?.as_table(),
+    }
+}
+
+fn section_mut<'a>(doc: &'a mut DocumentMut, person: Option<&str>) -> &'a mut Table {
+    let new_table = || Item::Table(Table::new());
+    let item = match person {
+        Some(person) => {
+            let people = doc.entry(PEOPLE).or_insert_with(|| {
+                let mut people = Table::new();
+                people.set_implicit(true);
+                Item::Table(people)
+            });
+            if !people.is_table() {
+                *people = new_table();
+            }
+            people[person].or_insert(new_table())
+        }
+        None => doc.entry(DAYS).or_insert_with(new_table),
+    };
+    if !item.is_table() {
+        *item = new_table();
+    }
+    item.as_table_mut().expect("section was just made a table")
+}
+
+fn parse_key(path: &Path, key: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(key, "%Y-%m-%d").map_err(|_| {
+        CalError::Config(format!(
+            "{}: {key:?} is not a date; use YYYY-MM-DD keys",
+            path.display()
+        ))
+    })
+}
+
+fn parse_entry(path: &Path, key: &str, item: &Item) -> Result<HolidayEntry> {
+    let invalid = |reason: &str| CalError::Config(format!("{}: {key}: {reason}", path.display()));
+    if let Some(name) = item.as_str() {
+        return Ok(HolidayEntry::custom(name));
+    }
+    let table = item
+        .as_table_like()
+        .ok_or_else(|| invalid("expected a name or a table"))?;
+    let name = table
+        .get("name")
+        .and_then(Item::as_str)
+        .ok_or_else(|| invalid("missing name"))?;
+    let kind = match table.get("kind").map(|kind| kind.as_str()) {
+        None | Some(Some("custom")) => HolidayKind::Custom,
+        Some(Some("leave")) => HolidayKind::Leave,
+        Some(_) => return Err(invalid("kind must be \"custom\" or \"leave\"")),
+    };
+    let half_day = match table.get("half_day") {
+        None => false,
+        Some(value) => value
+            .as_bool()
+            .ok_or_else(|| invalid("half_day must be true or false"))?,
+    };
+    let tags = match table.get("tags") {
+        None => Vec::new(),
+        Some(tags) => tags
+            .as_array()
+            .and_then(|tags| {
+                tags.iter()
+                    .map(|tag| tag.as_str().map(str::to_string))
+                    .collect()
+            })
+            .ok_or_else(|| invalid("tags must be a list of strings"))?,
+    };
+    for tag in &tags {
+        check_word(path, "tag", tag)?;
+    }
+    Ok(HolidayEntry {
+        kind,
+        half_day,
+        tags,
+        ..HolidayEntry::custom(name)
+    })
+}
+
+fn to_value(entry: &HolidayEntry) -> Value {
+    if entry.kind == HolidayKind::Custom && !entry.half_day && entry.tags.is_empty() {
+        return Value::from(entry.name.as_str());
+    }
+    let mut table = InlineTable::new();
+    table.insert("name", entry.name.as_str().into());
+    if entry.kind != HolidayKind::Custom {
+        table.insert("kind", entry.kind.label().into());
+    }
+    if entry.half_day {
+        table.insert("half_day", true.into());
+    }
+    if !entry.tags.is_empty() {
+        let tags: Array = entry.tags.iter().map(String::as_str).collect();
+        table.insert("tags", Value::Array(tags));
+    }
+    Value::InlineTable(table)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use std::time::SystemTime;
+
+    fn temp_file(label: &str, contents: &str) -> PathBuf {
+        let nanos = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .expect("clock after epoch")
+            .as_nanos();
+        let path = std::env::temp_dir().join(format!("cal2-team-{label}-{nanos}.toml"));
+        fs::write(&path, contents).expect("write team file");
+        path
+    }
+
+    const SAMPLE: &str = "\
+# Team calendar, reviewed in PRs.
+
+[days]
+2026-12-24 = \"Christmas Eve\" # office closes early
+2026-12-31 = { name = \"New Year's Eve\", half_day = true }
+2027-01-04 = \"Bridge day\"
+
+[people.alice]
+2026-11-03 = { name = \"Rotation\", tags = [\"oncall\"] }
+";
+
+    #[test]
+    fn load_reads_the_requested_year_and_person() {
+        let path = temp_file("load", SAMPLE);
+
+        let days = load(&path, 2026, None).expect("shared days");
+        assert_eq!(days.len(), 2);
+        assert_eq!(days[&(24, 12)], HolidayEntry::custom("Christmas Eve"));
+        assert!(days[&(31, 12)].half_day);
+
+        let alice = load(&path, 2026, Some("alice")).expect("alice's days");
+        assert_eq!(alice[&(3, 11)].tags, ["oncall"]);
+        assert!(load(&path, 2026, Some("bob")).expect("bob").is_empty());
+        assert_eq!(people(&path).expect("people"), ["alice"]);
+
+        fs::remove_file(&path).expect("cleanup");
+    }
+
+    #[test]
+    fn save_edits_entries_in_place() {
+        let path = temp_file("save", SAMPLE);
+
+        let mut hm = load(&path, 2026, None).expect("shared days");
+        hm.remove(&(31, 12));
+        hm.insert((7, 12), HolidayEntry::custom("Team offsite"));
+        hm.insert(
+            (24, 12),
+            HolidayEntry {
+                kind: HolidayKind::Leave,
+                ..HolidayEntry::custom("Christmas Eve")
+            },
+        );
+        save(&path, 2026, None, &hm).expect("save shared days");
+
+        let contents = fs::read_to_string(&path).expect("read back");
+        assert_eq!(
+            contents,
+            "\
+# Team calendar, reviewed in PRs.
+
+[days]
+2026-12-07 = \"Team offsite\"
+2026-12-24 = { name = \"Christmas Eve\", kind = \"leave\" } # office closes early
+2027-01-04 = \"Bridge day\"
+
+[people.alice]
+2026-11-03 = { name = \"Rotation\", tags = [\"oncall\"] }
+"
+        );
+
+        fs::remove_file(&path).expect("cleanup");
+    }
+
+    #[test]
+    fn save_creates_missing_file_and_person_sections() {
+        let path = temp_file("new", "");
+        fs::remove_file(&path).expect("start without a file");
+
+        let mut hm = HM::new();
+        hm.insert((28, 12), HolidayEntry::custom("Vacation"));
+        save(&path, 2026, Some("bob"), &hm).expect("save bob's days");
+
+        let contents = fs::read_to_string(&path).expect("read back");
+        assert_eq!(contents, "[people.bob]\n2026-12-28 = \"Vacation\"\n");
+        assert_eq!(load(&path, 2026, Some("bob")).expect("reload"), hm);
+
+        fs::remove_file(&path).expect("cleanup");
+    }
+
+    #[test]
+    fn load_reports_malformed_entries() {
+        let path = temp_file("bad", "[days]\nchristmas = \"Eve\"\n");
+        assert!(matches!(load(&path, 2026, None), Err(CalError::Config(_))));
+        fs::write(&path, "[days]\n2026-12-24 = { kind = \"leave\" }\n").expect("rewrite");
+        assert!(matches!(load(&path, 2026, None), Err(CalError::Config(_))));
+        fs::remove_file(&path).expect("cleanup");
+    }
+
+    #[test]
+    fn load_rejects_names_and_tags_that_options_cannot_match() {
+        let path = temp_file("case", "[people.Alice]\n2026-11-03 = \"Rotation\"\n");
+        for result in [
+            load(&path, 2026, Some("alice")).map(|_| ()),
+            people(&path).map(|_| ()),
+            save(&path, 2026, Some("alice"), &HM::new()),
+        ] {
+            assert!(matches!(result, Err(CalError::Config(_))));
+        }
+        assert!(
+            fs::read_to_string(&path)
+                .expect("unchanged")
+                .starts_with("[people.Alice]")
+        );
+
+        fs::write(
+            &path,
+            "[days]\n2026-11-03 = { name = \"Rotation\", tags = [\"OnCall\"] }\n",
+        )
+        .expect("rewrite");
+        assert!(matches!(load(&path, 2026, None), Err(CalError::Config(_))));
+        fs::remove_file(&path).expect("cleanup");
+    }
+}
@@ -0,0 +1,284 @@
+use crate::error::{CalError, Result};
+use chrono::format::{Item, StrftimeItems};
+use chrono::{Locale, NaiveDate};
+use std::fmt::{self, Write};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Template {
+    parts: Vec<Part>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Part {
+    Literal(String),
+    Field(Field, Option<String>),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Field {
+    Date,
+    Weekday,
+    Name,
+    Kind,
+    Provider,
+}
+
+pub struct Values<'a> {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+    pub name: &'a str,
+    pub kind: &'a str,
+    pub provider: &'a str,
+    pub locale: Option<Locale>,
+}
+
+impl Field {
+    fn parse(name: &str) -> Result<Self> {
+        match name {
+            "date" => Ok(Field::Date),
+            "weekday" => Ok(Field::Weekday),
+            "name" => Ok(Field::Name),
+            "kind" => Ok(Field::Kind),
+            "provider" => Ok(Field::Provider),
+            other => Err(CalError::Template(format!(
+                "unknown field {{{other}}}; expected one of date, weekday, name, kind, provider"
+            ))),
+        }
+    }
+
+    fn default_format(self) -> Option<&'static str> {
+        match self {
+            Field::Date => Some("%Y-%m-%d"),
+            Field::Weekday => Some("%A"),
+            Field::Name | Field::Kind | Field::Provider => None,
+        }
+    }
+}
+
+impl Template {
+    pub fn parse(source: &str) -> Result<Self> {
+        let mut parts = Vec::new();
+        let mut literal = String::new();
+        let mut chars = source.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '{' => {
+                    let mut placeholder = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => placeholder.push(c),
+                            None => {
+                                return Err(CalError::Template(format!(
+                                    "unclosed placeholder {{{placeholder}"
+                                )));
+                            }
+                        }
+                    }
+                    if !literal.is_empty() {
+                        parts.push(Part::Literal(std::mem::take(&mut literal)));
+                    }
+                    parts.push(Self::parse_placeholder(&placeholder)?);
+                }
+                '}' => {
+                    return Err(CalError::Template(
+                        "unmatched '}'; write '}}' for a literal brace".to_string(),
+                    ));
+                }
+                c => literal.push(c),
+            }
+        }
+        if !literal.is_empty() {
+            parts.push(Part::Literal(literal));
+        }
+        Ok(Self { parts })
+    }
+
+    fn parse_placeholder(placeholder: &str) -> Result<Part> {
+        let (name, format) = match placeholder.split_once(':') {
+            Some((name, format)) => (name.trim(), Some(format.to_string())),
+            None => (placeholder.trim(), None),
+        };
+        let field = Field::parse(name)?;
+        if let Some(format) = &format {
+            if field.default_format().is_none() {
+                return Err(CalError::Template(format!(
+                    "field {{{name}}} does not accept a format"
+                )));
+            }
+            // Time and offset specifiers parse fine but fail once applied to a
+            // date, so try the format on a fixed day as well.
+            let sample = NaiveDate::from_ymd_opt(2000, 1, 1).expect("valid sample date");
+            if StrftimeItems::new(format).any(|item| matches!(item, Item::Error))
+                || format_date(&mut String::new(), sample, format, None).is_err()
+            {
+                return Err(CalError::Template(format!(
+                    "invalid date format {format:?} in {{{placeholder}}}"
+                )));
+            }
+        }
+        Ok(Part::Field(field, format))
+    }
+
+    pub fn uses_provider(&self) -> bool {
+        self.parts
+            .iter()
+            .any(|part| matches!(part, Part::Field(Field::Provider, _)))
+    }
+
+    pub fn render(&self, values: &Values) -> Result<String> {
+        let date = NaiveDate::from_ymd_opt(values.year, values.month, values.day);
+        let mut rendered = String::new();
+        for part in &self.parts {
+            match part {
+                Part::Literal(text) => rendered.push_str(text),
+                Part::Field(field, format) => match (field, date) {
+                    (Field::Date | Field::Weekday, Some(date)) => {
+                        let format = format
+                            .as_deref()
+                            .or(field.default_format())
+                            .unwrap_or_default();
+                        format_date(&mut rendered, date, format, values.locale).map_err(|_| {
+                            CalError::Template(format!("cannot format date with {format:?}"))
+                        })?;
+                    }
+                    (Field::Date, None) => rendered.push_str(&format!(
+                        "{}-{:02}-{:02}",
+                        values.year, values.month, values.day
+                    )),
+                    (Field::Weekday, None) => {}
+                    (Field::Name, _) => rendered.push_str(values.name),
+                    (Field::Kind, _) => rendered.push_str(values.kind),
+                    (Field::Provider, _) => rendered.push_str(values.provider),
+                },
+            }
+        }
+        Ok(rendered)
+    }
+}
+
+fn format_date(
+    out: &mut String,
+    date: NaiveDate,
+    format: &str,
+    locale: Option<Locale>,
+) -> fmt::Result {
+    match locale {
+        Some(locale) => write!(out, "{}", date.format_localized(format, locale)),
+        None => write!(out, "{}", date.format(format)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values<'a>(day: u32, month: u32, name: &'a str) -> Values<'a> {
+        Values {
+            year: 2026,
+            month,
+            day,
+            name,
+            kind: "official",
+            provider: "argentina-datos",
+            locale: None,
+        }
+    }
+
+    #[test]
+    fn render_substitutes_fields_with_date_formats() {
+        let template =
+            Template::parse("{date:%a %d %b} {name} ({kind})").expect("template should parse");
+        assert_eq!(
+            template
+                .render(&values(25, 12, "Navidad"))
+                .expect("render should succeed"),
+            "Fri 25 Dec Navidad (official)"
+        );
+    }
+
+    #[test]
+    fn render_uses_default_formats_and_escaped_braces() {
+        let template =
+            Template::parse("{{{date}}} {weekday} via {provider}").expect("template should parse");
+        assert_eq!(
+            template
+                .render(&values(1, 1, "New Year"))
+                .expect("render should succeed"),
+            "{2026-01-01} Thursday via argentina-datos"
+        );
+    }
+
+    #[test]
+    fn uses_provider_reports_the_provider_field() {
+        assert!(
+            Template::parse("{date} {provider}")
+                .expect("valid")
+                .uses_provider()
+        );
+        assert!(
+            !Template::parse("{date} {name}")
+                .expect("valid")
+                .uses_provider()
+        );
+    }
+
+    #[test]
+    fn render_localizes_date_formats() {
+        let template = Template::parse("{date:%A %e %B}").expect("template should parse");
+        let values = Values {
+            locale: Some(Locale::es_AR),
+            ..values(25, 12, "Navidad")
+        };
+        assert_eq!(
+            template.render(&values).expect("render should succeed"),
+            "viernes 25 diciembre"
+        );
+    }
+
+    #[test]
+    fn render_falls_back_for_impossible_dates() {
+        let template = Template::parse("{date:%d/%m} {weekday}").expect("template should parse");
+        assert_eq!(
+            template
+                .render(&values(31, 2, "Broken"))
+                .expect("render should succeed"),
+            "2026-02-31 "
+        );
+    }
+
+    #[test]
+    fn parse_rejects_invalid_templates() {
+        for source in ["{unknown}", "{name", "name}", "{name:%d}", "{date:%Q}"] {
+            assert!(
+                matches!(Template::parse(source), Err(CalError::Template(_))),
+                "expected template error for {source:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn parse_rejects_time_and_offset_specifiers() {
+        for source in [
+            "{date:%H}",
+            "{date:%M:%S}",
+            "{weekday:%z}",
+            "{date:%Z}",
+            "{date:%s}",
+        ] {
+            assert!(
+                matches!(Template::parse(source), Err(CalError::Template(_))),
+                "expected template error for {source:?}"
+            );
+        }
+    }
+}